@@ -0,0 +1,353 @@
+//! Synchronous API, in the spirit of `reqwest::blocking`.
+//!
+//! Every type here wraps its asynchronous counterpart and drives it on a private tokio runtime,
+//! so the application doesn't need its own runtime. Model types (settings, users, etc.) are
+//! shared with the asynchronous API, and the wrapped object is accessible through `Deref`, e.g.
+//! to call its getters.
+//!
+//! **N.B.** The blocking types must not be used within an asynchronous runtime, otherwise
+//! they panic.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use plex_api::blocking::MyPlexAccount;
+//!
+//! let account = MyPlexAccount::by_token("auth-token").expect("Unable to log in");
+//! for device in account.get_resources().expect("Unable to get resources") {
+//!     if let Ok(srv) = device.connect_to_server() {
+//!         println!("Connected to {}, version {}", device.get_name(), srv.get_version());
+//!     }
+//! }
+//! ```
+
+use futures::StreamExt;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use crate::{
+    DirectoryMediaContainer, MediaMetadata, MetadataStream, Pagination, PlexClient, Privacy,
+    Result, SettingsMediaContainer, User,
+};
+
+fn new_runtime() -> Result<Arc<Runtime>> {
+    Ok(Arc::new(
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?,
+    ))
+}
+
+macro_rules! impl_deref {
+    ($name:ident, $target:ty) => {
+        impl Deref for $name {
+            type Target = $target;
+
+            fn deref(&self) -> &Self::Target {
+                &self.inner
+            }
+        }
+    };
+}
+
+/// Synchronous counterpart of [`crate::MyPlexAccount`].
+#[derive(Debug)]
+pub struct MyPlexAccount {
+    inner: crate::MyPlexAccount,
+    runtime: Arc<Runtime>,
+}
+
+impl_deref!(MyPlexAccount, crate::MyPlexAccount);
+
+impl DerefMut for MyPlexAccount {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl MyPlexAccount {
+    fn block_on<F: Future<Output = Result<crate::MyPlexAccount>>>(future: F) -> Result<Self> {
+        let runtime = new_runtime()?;
+        let inner = runtime.block_on(future)?;
+        Ok(MyPlexAccount { inner, runtime })
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using username and password.
+    pub fn login(username: &str, password: &str) -> Result<Self> {
+        Self::block_on(crate::MyPlexAccount::login(username, password))
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using username and password, communicating
+    /// through the provided client.
+    pub fn login_with_client(client: &PlexClient, username: &str, password: &str) -> Result<Self> {
+        Self::block_on(crate::MyPlexAccount::login_with_client(
+            client, username, password,
+        ))
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using existing authentication token.
+    pub fn by_token(auth_token: &str) -> Result<Self> {
+        Self::block_on(crate::MyPlexAccount::by_token(auth_token))
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using existing authentication token, communicating
+    /// through the provided client.
+    pub fn by_token_with_client(client: &PlexClient, auth_token: &str) -> Result<Self> {
+        Self::block_on(crate::MyPlexAccount::by_token_with_client(
+            client, auth_token,
+        ))
+    }
+
+    /// Returns the wrapped asynchronous account.
+    pub fn into_inner(self) -> crate::MyPlexAccount {
+        self.inner
+    }
+
+    /// See [`crate::MyPlexAccount::get_claim_token`].
+    pub fn get_claim_token(&self) -> Result<String> {
+        self.runtime.block_on(self.inner.get_claim_token())
+    }
+
+    /// See [`crate::MyPlexAccount::get_devices`].
+    pub fn get_devices(&self) -> Result<Vec<Device>> {
+        let devices = self.runtime.block_on(self.inner.get_devices())?;
+        Ok(self.wrap_devices(devices))
+    }
+
+    /// See [`crate::MyPlexAccount::get_resources`].
+    pub fn get_resources(&self) -> Result<Vec<Device>> {
+        let devices = self.runtime.block_on(self.inner.get_resources())?;
+        Ok(self.wrap_devices(devices))
+    }
+
+    /// See [`crate::MyPlexAccount::get_users`].
+    pub fn get_users(&self) -> Result<Vec<User>> {
+        self.runtime.block_on(self.inner.get_users())
+    }
+
+    /// See [`crate::MyPlexAccount::get_privacy`].
+    pub fn get_privacy(&self) -> Result<Privacy> {
+        self.runtime.block_on(self.inner.get_privacy())
+    }
+
+    /// See [`crate::MyPlexAccount::set_privacy`].
+    pub fn set_privacy(&self, opt_out_playback: bool, opt_out_library_stats: bool) -> Result<()> {
+        self.runtime.block_on(
+            self.inner
+                .set_privacy(opt_out_playback, opt_out_library_stats),
+        )
+    }
+
+    /// See [`crate::MyPlexAccount::get_webhooks`].
+    pub fn get_webhooks(&self) -> Result<Vec<String>> {
+        self.runtime.block_on(self.inner.get_webhooks())
+    }
+
+    /// See [`crate::MyPlexAccount::set_webhooks`].
+    pub fn set_webhooks(&self, webhooks: &[&str]) -> Result<()> {
+        self.runtime.block_on(self.inner.set_webhooks(webhooks))
+    }
+
+    /// See [`crate::MyPlexAccount::add_webhook`].
+    pub fn add_webhook(&self, webhook: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.add_webhook(webhook))
+    }
+
+    /// See [`crate::MyPlexAccount::del_webhook`].
+    pub fn del_webhook(&self, webhook: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.del_webhook(webhook))
+    }
+
+    fn wrap_devices(&self, devices: Vec<crate::Device>) -> Vec<Device> {
+        devices
+            .into_iter()
+            .map(|inner| Device {
+                inner,
+                runtime: self.runtime.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Synchronous counterpart of [`crate::Device`].
+#[derive(Debug)]
+pub struct Device {
+    inner: crate::Device,
+    runtime: Arc<Runtime>,
+}
+
+impl_deref!(Device, crate::Device);
+
+impl Device {
+    /// Returns the wrapped asynchronous device.
+    pub fn into_inner(self) -> crate::Device {
+        self.inner
+    }
+
+    /// See [`crate::Device::connect_to_server`].
+    pub fn connect_to_server(&self) -> Result<Server> {
+        let inner = self.runtime.block_on(self.inner.connect_to_server())?;
+        Ok(Server {
+            inner,
+            runtime: self.runtime.clone(),
+        })
+    }
+}
+
+/// Synchronous counterpart of [`crate::Server`].
+#[derive(Debug)]
+pub struct Server {
+    inner: crate::Server,
+    runtime: Arc<Runtime>,
+}
+
+impl_deref!(Server, crate::Server);
+
+impl DerefMut for Server {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl Server {
+    /// Establish a connection with the server, see [`crate::Server::connect`].
+    pub fn connect(url: &str, auth_token: &str) -> Result<Self> {
+        let runtime = new_runtime()?;
+        let inner = runtime.block_on(crate::Server::connect(url, auth_token))?;
+        Ok(Server { inner, runtime })
+    }
+
+    /// Establish a connection with the server, communicating through the provided client.
+    pub fn connect_with_client(client: &PlexClient, url: &str, auth_token: &str) -> Result<Self> {
+        let runtime = new_runtime()?;
+        let inner =
+            runtime.block_on(crate::Server::connect_with_client(client, url, auth_token))?;
+        Ok(Server { inner, runtime })
+    }
+
+    /// Returns the wrapped asynchronous server.
+    pub fn into_inner(self) -> crate::Server {
+        self.inner
+    }
+
+    /// See [`crate::Server::refresh`].
+    pub fn refresh(&mut self) -> Result<()> {
+        self.runtime.block_on(self.inner.refresh())
+    }
+
+    /// See [`crate::Server::claim`].
+    pub fn claim(&mut self, claim_token: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.claim(claim_token))
+    }
+
+    /// See [`crate::Server::unclaim`].
+    pub fn unclaim(&mut self) -> Result<()> {
+        self.runtime.block_on(self.inner.unclaim())
+    }
+
+    /// See [`crate::Server::get_settings`].
+    pub fn get_settings(&self) -> Result<SettingsMediaContainer> {
+        self.runtime.block_on(self.inner.get_settings())
+    }
+
+    /// See [`crate::Server::update_settings`].
+    pub fn update_settings(&self, settings: &SettingsMediaContainer) -> Result<()> {
+        self.runtime.block_on(self.inner.update_settings(settings))
+    }
+
+    /// See [`crate::Server::get_sections`].
+    pub fn get_sections(&self) -> Result<Library> {
+        let inner = self.runtime.block_on(self.inner.get_sections())?;
+        Ok(Library {
+            inner,
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// See [`crate::Server::get_recently_added`].
+    pub fn get_recently_added(&self) -> Result<Library> {
+        let inner = self.runtime.block_on(self.inner.get_recently_added())?;
+        Ok(Library {
+            inner,
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// See [`crate::Server::get_on_deck`].
+    pub fn get_on_deck(&self) -> Result<Library> {
+        let inner = self.runtime.block_on(self.inner.get_on_deck())?;
+        Ok(Library {
+            inner,
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// See [`crate::Server::stream_metadata`].
+    pub fn stream_metadata(&self, path: &str, pagination: Pagination) -> MetadataIter {
+        MetadataIter {
+            stream: self.inner.stream_metadata(path, pagination),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`crate::Server::stream_recently_added`].
+    pub fn stream_recently_added(&self, pagination: Pagination) -> MetadataIter {
+        MetadataIter {
+            stream: self.inner.stream_recently_added(pagination),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// Synchronous counterpart of [`crate::Library`].
+#[derive(Debug, Clone)]
+pub struct Library {
+    inner: crate::Library,
+    runtime: Arc<Runtime>,
+}
+
+impl_deref!(Library, crate::Library);
+
+impl Library {
+    /// Returns the wrapped asynchronous library.
+    pub fn into_inner(self) -> crate::Library {
+        self.inner
+    }
+
+    /// See [`crate::Library::stream_section_items`].
+    pub fn stream_section_items(
+        &self,
+        section: &DirectoryMediaContainer,
+        pagination: Pagination,
+    ) -> MetadataIter {
+        MetadataIter {
+            stream: self.inner.stream_section_items(section, pagination),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// Synchronous counterpart of [`crate::MetadataStream`], loading the next page when the
+/// current one is exhausted.
+pub struct MetadataIter {
+    stream: MetadataStream,
+    runtime: Arc<Runtime>,
+}
+
+impl fmt::Debug for MetadataIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetadataIter").finish()
+    }
+}
+
+impl Iterator for MetadataIter {
+    type Item = Result<MediaMetadata>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stream = &mut self.stream;
+        self.runtime.block_on(stream.next())
+    }
+}
@@ -1,10 +1,16 @@
-use crate::SettingValue;
+use crate::{Response, SettingValue};
 use reqwest::header::InvalidHeaderValue;
+use reqwest::StatusCode;
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::{PoisonError, RwLockReadGuard, RwLockWriteGuard};
 use thiserror::Error;
 use url::Url;
 
+/// Code of plex.tv error, returned on login when the account has two-factor authentication
+/// enabled, but the verification code wasn't provided.
+const OTP_REQUIRED_ERROR_CODE: i32 = 1029;
+
 #[derive(Debug, Error)]
 pub enum PlexApiError {
     #[error("Unable to perform a HTTP-request: {source}")]
@@ -26,23 +32,65 @@ pub enum PlexApiError {
     RWLockReadPoison,
     #[error("RwLock was poisoned (writing)")]
     RWLockWritePoison,
-    #[error("Error while communicating with MyPlexApi: {errors:?}")]
-    MyPlexErrorResponse { errors: Vec<PlexApiError> },
-    #[error("Error occurred while communicating to MyPlex API: #{code} - {message}")]
-    MyPlexApiError { code: i32, message: String },
-    #[error("Unable to get the claim token: {0}")]
-    FailedToGetClaimToken(String),
+    #[error("Unable to serialize form parameters: {source}")]
+    FormSerializeError {
+        #[from]
+        source: serde_urlencoded::ser::Error,
+    },
     #[error("Unable to parse request URL: {source}")]
     UrlParseError {
         #[from]
         source: url::ParseError,
     },
+    #[error("No recorded interaction found for {method} {url}")]
+    CassetteInteractionNotFound { method: String, url: String },
+    #[error("Cassette's mutex was poisoned")]
+    CassetteLockPoisoned,
+    #[error("Unable to parse header name: {source}")]
+    InvalidHeaderName {
+        #[from]
+        source: reqwest::header::InvalidHeaderName,
+    },
+    #[error("Invalid status code in the cassette: {0}")]
+    CassetteInvalidStatusCode(u16),
+    #[error("The object wasn't obtained through a PlexClient, so it can't make requests")]
+    ClientIsNotSet,
     #[error("Failed to delete the object because delete_url is unknown for it")]
     DeleteUrlIsNotProvided,
     #[error("Requested webhook not found ({url})")]
     WebhookNotFound { url: String },
-    #[error("Received an unexpected response from API: {0}")]
-    UnexpectedApiResponse(String),
+    #[error("The PIN {code} has expired before it was linked to an account")]
+    PinExpired { code: String },
+    #[error("User {id} is not found among the friends")]
+    UserNotFound { id: u32 },
+    #[error("Server {server_id} is not shared with the user {user_id}")]
+    ServerNotShared { user_id: u32, server_id: u32 },
+    #[error("The share for {invited} is missing in the response")]
+    SharedServerMissing { invited: String },
+    #[error("The session has expired at {expired_at}, signing in again is required")]
+    SessionExpired {
+        expired_at: chrono::DateTime<chrono::Utc>,
+    },
+    #[cfg(feature = "keyring")]
+    #[error("Unable to access the keyring: {source}")]
+    KeyringError {
+        #[from]
+        source: keyring::KeyringError,
+    },
+    #[error("Authentication required or the token is invalid: {0}")]
+    Unauthorized(StatusError),
+    #[error("Verification code is required for the account with two-factor authentication: {0}")]
+    OtpRequired(StatusError),
+    #[error("Access denied: {0}")]
+    Forbidden(StatusError),
+    #[error("Requested resource not found: {0}")]
+    NotFound(StatusError),
+    #[error("Too many requests: {0}")]
+    TooManyRequests(StatusError),
+    #[error("Server error: {0}")]
+    ServerError(StatusError),
+    #[error("Unexpected response status: {0}")]
+    UnexpectedStatus(StatusError),
     #[error("You've called update_settings(), but no settings were actually updated in provided container")]
     NoChangedSettingsFound,
     #[error("The device doesn't provide 'server' (but provides following: {provides:?})")]
@@ -61,10 +109,6 @@ pub enum PlexApiError {
     ExpectedSettingValueText { provided: SettingValue },
     #[error("SettingValue::Double was expected, but provided another: {provided:?}")]
     ExpectedSettingValueDouble { provided: SettingValue },
-    #[error("Unexpected error occurred during unclaiming the server: {0}")]
-    UnexpectedUnclaimError(String),
-    #[error("Unexpected error occurred during claiming the server: {0}")]
-    UnexpectedClaimError(String),
     #[error("Claim token must be provided")]
     ClaimTokenEmpty,
     #[error("Incorrect claim token provided")]
@@ -92,6 +136,204 @@ pub enum PlexApiError {
     },
 }
 
+impl PlexApiError {
+    /// Returns an error, matching the status of the unsuccessful response.
+    pub(crate) fn from_response(response: &Response) -> Self {
+        let error = StatusError {
+            status: response.status(),
+            url: response.url().map(Url::to_string).unwrap_or_default(),
+            body: ErrorBody::parse(&response.text()),
+        };
+
+        match error.status {
+            StatusCode::UNAUTHORIZED if error.body.has_code(OTP_REQUIRED_ERROR_CODE) => {
+                PlexApiError::OtpRequired(error)
+            }
+            StatusCode::UNAUTHORIZED => PlexApiError::Unauthorized(error),
+            StatusCode::FORBIDDEN => PlexApiError::Forbidden(error),
+            StatusCode::NOT_FOUND => PlexApiError::NotFound(error),
+            StatusCode::TOO_MANY_REQUESTS => PlexApiError::TooManyRequests(error),
+            status if status.is_server_error() => PlexApiError::ServerError(error),
+            _ => PlexApiError::UnexpectedStatus(error),
+        }
+    }
+
+    /// Returns the details of unsuccessful response, if the error was caused by one.
+    pub const fn get_status_error(&self) -> Option<&StatusError> {
+        match self {
+            PlexApiError::Unauthorized(e)
+            | PlexApiError::OtpRequired(e)
+            | PlexApiError::Forbidden(e)
+            | PlexApiError::NotFound(e)
+            | PlexApiError::TooManyRequests(e)
+            | PlexApiError::ServerError(e)
+            | PlexApiError::UnexpectedStatus(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if repeating the same request later might succeed: the request was
+    /// throttled, the server failed or was unreachable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PlexApiError::TooManyRequests(_) | PlexApiError::ServerError(_) => true,
+            PlexApiError::ReqwestError { source } => source.is_timeout() || source.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the token is missing, invalid or expired, or isn't allowed to perform
+    /// the request, e.g. for a shared or managed user.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            PlexApiError::Unauthorized(_)
+                | PlexApiError::OtpRequired(_)
+                | PlexApiError::Forbidden(_)
+        )
+    }
+}
+
+/// Details of an unsuccessful response.
+#[derive(Debug, Clone)]
+pub struct StatusError {
+    status: StatusCode,
+    url: String,
+    body: ErrorBody,
+}
+
+impl StatusError {
+    pub const fn get_status(&self) -> StatusCode {
+        self.status
+    }
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+    pub const fn get_body(&self) -> &ErrorBody {
+        &self.body
+    }
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} from {}", self.status, self.url)?;
+        if !self.body.is_empty() {
+            write!(f, ": {}", self.body)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single error, reported in the body of unsuccessful response.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ErrorMessage {
+    #[serde(default)]
+    code: Option<i32>,
+    #[serde(alias = "status")]
+    message: String,
+}
+
+impl ErrorMessage {
+    pub const fn get_code(&self) -> Option<i32> {
+        self.code
+    }
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Body of unsuccessful response: errors, parsed from JSON (`{"errors": [...]}`) or XML
+/// (`<errors><error .../></errors>` or `<Response code="..." status="..."/>`), and the raw text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ErrorBody {
+    errors: Vec<ErrorMessage>,
+    raw: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorsContainer {
+    #[serde(default, alias = "error")]
+    errors: Vec<ErrorMessage>,
+}
+
+/// Error, reported as `{"error": "..."}`, e.g. by claim token API.
+#[derive(Deserialize)]
+struct SingleError {
+    error: String,
+}
+
+impl ErrorBody {
+    pub(crate) fn parse(raw: &str) -> Self {
+        let trimmed = raw.trim_start();
+        let errors = if trimmed.starts_with('{') {
+            serde_json::from_str::<ErrorsContainer>(trimmed)
+                .map(|c| c.errors)
+                .or_else(|_| {
+                    serde_json::from_str::<SingleError>(trimmed).map(|e| {
+                        vec![ErrorMessage {
+                            code: None,
+                            message: e.error,
+                        }]
+                    })
+                })
+                .unwrap_or_default()
+        } else if trimmed.starts_with("<errors") || trimmed.starts_with("<?xml") {
+            quick_xml::de::from_str::<ErrorsContainer>(trimmed)
+                .map(|c| c.errors)
+                .or_else(|_| quick_xml::de::from_str::<ErrorMessage>(trimmed).map(|e| vec![e]))
+                .unwrap_or_default()
+        } else if trimmed.starts_with("<Response") {
+            quick_xml::de::from_str::<ErrorMessage>(trimmed)
+                .map(|e| vec![e])
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+
+        ErrorBody {
+            errors,
+            raw: raw.to_owned(),
+        }
+    }
+
+    /// Returns errors, reported by the API.
+    pub fn get_errors(&self) -> &[ErrorMessage] {
+        &self.errors
+    }
+
+    /// Returns the body as it was received.
+    pub fn get_raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns `true` if any of the reported errors has the provided code.
+    pub fn has_code(&self, code: i32) -> bool {
+        self.errors.iter().any(|e| e.code == Some(code))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.errors.is_empty() && self.raw.trim().is_empty()
+    }
+}
+
+impl fmt::Display for ErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.errors.is_empty() {
+            return write!(f, "{}", self.raw.trim());
+        }
+
+        let messages: Vec<_> = self
+            .errors
+            .iter()
+            .map(|e| match e.code {
+                Some(code) => format!("#{} - {}", code, e.message),
+                None => e.message.clone(),
+            })
+            .collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
 impl<T> From<std::sync::PoisonError<RwLockWriteGuard<'_, T>>> for PlexApiError {
     fn from(_: PoisonError<RwLockWriteGuard<'_, T>>) -> Self {
         PlexApiError::RWLockWritePoison
@@ -0,0 +1,295 @@
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, CACHE_CONTROL, ETAG, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::StatusCode;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use crate::http::{Request, Response};
+
+/// Storage for responses, used by [`PlexClient`] to avoid downloading unchanged data.
+///
+/// Keys are opaque hashes of the request's url, token and `Accept` header, so the storage never
+/// sees the tokens.
+///
+/// [`PlexClient`]: struct.PlexClient.html
+pub trait ResponseCache: Debug + Send + Sync {
+    /// Returns a stored response by the key.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+    /// Stores the response, replacing the previous one with the same key.
+    fn put(&self, key: &str, response: CachedResponse);
+    /// Removes the stored response, if there is one.
+    fn remove(&self, key: &str);
+}
+
+/// A response, stored in [`ResponseCache`].
+///
+/// [`ResponseCache`]: trait.ResponseCache.html
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    #[serde(with = "base64_body")]
+    body: Vec<u8>,
+    fresh_until: Option<DateTime<Utc>>,
+}
+
+impl CachedResponse {
+    /// Returns `true` if the response can be used without revalidation.
+    pub fn is_fresh(&self) -> bool {
+        self.fresh_until.is_some_and(|until| until > Utc::now())
+    }
+
+    fn etag(&self) -> Option<&str> {
+        self.headers.get(ETAG.as_str()).map(String::as_str)
+    }
+
+    fn last_modified(&self) -> Option<&str> {
+        self.headers.get(LAST_MODIFIED.as_str()).map(String::as_str)
+    }
+
+    fn to_response(&self) -> Response {
+        let mut headers = HeaderMap::new();
+        for (key, value) in &self.headers {
+            if let (Ok(key), Ok(value)) = (HeaderName::from_str(key), HeaderValue::from_str(value))
+            {
+                headers.insert(key, value);
+            }
+        }
+
+        Response::new(
+            StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK),
+            headers,
+            self.body.clone(),
+        )
+    }
+}
+
+/// [`ResponseCache`] keeping the responses in memory. Clones share the storage.
+///
+/// [`ResponseCache`]: trait.ResponseCache.html
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryCache {
+    entries: Arc<Mutex<HashMap<String, CachedResponse>>>,
+}
+
+impl ResponseCache for InMemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.lock().ok()?.get(key).cloned()
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key.to_owned(), response);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(key);
+        }
+    }
+}
+
+/// [`ResponseCache`] keeping every response as a JSON-file in the directory, so the cache
+/// survives restarts of the application.
+///
+/// [`ResponseCache`]: trait.ResponseCache.html
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Creates the cache in the directory, creating the directory if needed.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> crate::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(DiskCache { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+impl ResponseCache for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let result = serde_json::to_string(&response)
+            .map_err(crate::PlexApiError::from)
+            .and_then(|content| Ok(fs::write(self.path(key), content)?));
+        if let Err(e) = result {
+            warn!("Unable to store the response in the cache: {}", e);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let path = self.path(key);
+        if path.exists() {
+            if let Err(e) = fs::remove_file(path) {
+                warn!("Unable to remove the response from the cache: {}", e);
+            }
+        }
+    }
+}
+
+/// Keeps the body as base64, so binary responses, e.g. images, survive the JSON encoding.
+mod base64_body {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(body: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&base64::encode(body))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        let body = String::deserialize(d)?;
+        base64::decode(body).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returns the cache key for the request, based on its url, token and `Accept` header.
+pub(crate) fn cache_key(request: &Request) -> String {
+    let headers = request.get_headers();
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+    };
+
+    // FNV-1a, which is stable between runs, unlike `DefaultHasher`
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in &[
+        request.get_url().as_str(),
+        header("X-Plex-Token"),
+        header(ACCEPT.as_str()),
+    ] {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Adds conditional headers to the request, to revalidate the cached response.
+pub(crate) fn add_conditional_headers(request: &mut Request, cached: &CachedResponse) {
+    let headers = request.headers_mut();
+    if let Some(etag) = cached.etag().and_then(|v| HeaderValue::from_str(v).ok()) {
+        headers.insert(IF_NONE_MATCH, etag);
+    }
+    if let Some(date) = cached
+        .last_modified()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        headers.insert(IF_MODIFIED_SINCE, date);
+    }
+}
+
+/// Removes conditional headers from the request, so the full response is sent.
+pub(crate) fn remove_conditional_headers(request: &mut Request) {
+    let headers = request.headers_mut();
+    headers.remove(IF_NONE_MATCH);
+    headers.remove(IF_MODIFIED_SINCE);
+}
+
+/// Handles a response to the (possibly conditional) request: returns the cached response on
+/// `304 Not Modified` and stores cacheable responses.
+pub(crate) fn handle_response(
+    cache: &dyn ResponseCache,
+    key: &str,
+    cached: Option<CachedResponse>,
+    response: Response,
+) -> Response {
+    let directives = CacheDirectives::from(response.headers());
+
+    match (response.status(), cached) {
+        (StatusCode::NOT_MODIFIED, Some(mut cached)) => {
+            cached.fresh_until = directives.fresh_until();
+            let response = cached.to_response();
+            cache.put(key, cached);
+            response
+        }
+        (StatusCode::OK, _) if directives.is_storable(response.headers()) => {
+            let cached = CachedResponse {
+                status: response.status().as_u16(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.as_str().to_owned(), v.to_str().ok()?.to_owned())))
+                    .collect(),
+                body: response.bytes().to_vec(),
+                fresh_until: directives.fresh_until(),
+            };
+            cache.put(key, cached);
+            response
+        }
+        _ => response,
+    }
+}
+
+/// Returns a stored response, which can be used without sending the request.
+pub(crate) fn fresh_response(cached: &CachedResponse) -> Option<Response> {
+    if cached.is_fresh() {
+        Some(cached.to_response())
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheDirectives {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<i64>,
+}
+
+impl CacheDirectives {
+    fn fresh_until(&self) -> Option<DateTime<Utc>> {
+        if self.no_cache {
+            return None;
+        }
+        self.max_age
+            .map(|max_age| Utc::now() + Duration::seconds(max_age))
+    }
+
+    /// Only responses, which can be revalidated or have a lifetime, are worth storing.
+    fn is_storable(&self, headers: &HeaderMap) -> bool {
+        !self.no_store
+            && (self.max_age.is_some()
+                || headers.contains_key(ETAG)
+                || headers.contains_key(LAST_MODIFIED))
+    }
+}
+
+impl From<&HeaderMap> for CacheDirectives {
+    fn from(headers: &HeaderMap) -> Self {
+        let mut directives = CacheDirectives::default();
+        for value in headers.get_all(CACHE_CONTROL) {
+            for directive in value.to_str().unwrap_or_default().split(',') {
+                let directive = directive.trim().to_lowercase();
+                match directive.as_str() {
+                    "no-store" => directives.no_store = true,
+                    "no-cache" => directives.no_cache = true,
+                    _ => {
+                        if let Some(max_age) = directive.strip_prefix("max-age=") {
+                            directives.max_age = max_age.trim_matches('"').parse().ok();
+                        }
+                    }
+                }
+            }
+        }
+        directives
+    }
+}
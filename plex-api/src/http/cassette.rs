@@ -0,0 +1,335 @@
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+use crate::http::{HttpTransport, Request, Response};
+use crate::{PlexApiError, Result};
+
+/// Value, which replaces every secret in recorded interactions.
+pub const REDACTED: &str = "REDACTED";
+
+/// Headers, which values are never written to a cassette.
+const SECRET_HEADERS: [&str; 4] = ["x-plex-token", "authorization", "cookie", "set-cookie"];
+
+/// Headers, which identify the machine the cassette was recorded on. They're replaced with
+/// [`REDACTED`] as well, so cassettes don't depend on the recording environment.
+///
+/// [`REDACTED`]: constant.REDACTED.html
+const IDENTITY_HEADERS: [&str; 3] = [
+    "x-plex-device",
+    "x-plex-device-name",
+    "x-plex-platform-version",
+];
+
+/// Query and form parameters, which values are never written to a cassette.
+const SECRET_PARAMS: [&str; 5] = [
+    "X-Plex-Token",
+    "token",
+    "password",
+    "verificationCode",
+    "pin",
+];
+
+/// JSON-keys and XML-attributes in bodies, which values are never written to a cassette.
+const SECRET_FIELDS: [&str; 4] = ["authToken", "authenticationToken", "accessToken", "token"];
+
+/// A list of recorded request/response pairs, stored as a JSON-file.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+impl Cassette {
+    /// Loads a cassette from the file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Saves the cassette to the file, replacing its content.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the number of recorded interactions.
+    pub fn len(&self) -> usize {
+        self.interactions.len()
+    }
+
+    /// Returns `true` if nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.interactions.is_empty()
+    }
+
+    fn push(&mut self, request: &Request, response: &Response) {
+        self.interactions.push(Interaction {
+            request: RecordedRequest {
+                method: request.get_method().to_string(),
+                url: scrub_url(request.get_url()),
+                headers: scrub_headers(request.get_headers()),
+                body: request
+                    .get_body()
+                    .map(|body| scrub_params(&String::from_utf8_lossy(body))),
+            },
+            response: RecordedResponse {
+                status: response.status().as_u16(),
+                headers: scrub_headers(response.headers()),
+                body: scrub_body(&response.text()),
+            },
+        });
+    }
+}
+
+/// [`HttpTransport`], which performs the requests through another transport and writes every
+/// interaction to a [`Cassette`] file, with tokens, passwords and other secrets replaced by
+/// [`REDACTED`].
+///
+/// The file is rewritten after each request, so nothing is lost if the process is interrupted.
+///
+/// [`HttpTransport`]: trait.HttpTransport.html
+/// [`Cassette`]: struct.Cassette.html
+/// [`REDACTED`]: constant.REDACTED.html
+#[derive(Debug, Clone)]
+pub struct RecordingTransport {
+    inner: Arc<dyn HttpTransport>,
+    path: PathBuf,
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl RecordingTransport {
+    /// Creates a transport recording to the provided file. Existing file would be overwritten.
+    pub fn new<T: HttpTransport + 'static, P: Into<PathBuf>>(inner: T, path: P) -> Self {
+        RecordingTransport {
+            inner: Arc::new(inner),
+            path: path.into(),
+            cassette: Arc::new(Mutex::new(Cassette::default())),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for RecordingTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let response = self.inner.execute(request.clone()).await?;
+
+        let mut cassette = self
+            .cassette
+            .lock()
+            .map_err(|_| PlexApiError::CassetteLockPoisoned)?;
+        cassette.push(&request, &response);
+        cassette.save(&self.path)?;
+
+        Ok(response)
+    }
+}
+
+/// [`HttpTransport`], which serves responses from a [`Cassette`] without any network access.
+///
+/// Requests are matched by method, path and query, the host is ignored, so a cassette recorded
+/// against one server can be replayed with any url. Identical requests get the recorded
+/// responses in the recorded order, the last one is repeated once the others are used.
+///
+/// [`HttpTransport`]: trait.HttpTransport.html
+/// [`Cassette`]: struct.Cassette.html
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    interactions: Arc<Mutex<Vec<(Interaction, bool)>>>,
+}
+
+impl ReplayTransport {
+    /// Creates a transport, replaying the cassette from the file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::from(Cassette::load(path)?))
+    }
+}
+
+impl From<Cassette> for ReplayTransport {
+    fn from(cassette: Cassette) -> Self {
+        ReplayTransport {
+            interactions: Arc::new(Mutex::new(
+                cassette
+                    .interactions
+                    .into_iter()
+                    .map(|i| (i, false))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReplayTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let method = request.get_method().to_string();
+        let url = scrub_url(request.get_url());
+        let path = path_and_query(&url);
+
+        let mut interactions = self
+            .interactions
+            .lock()
+            .map_err(|_| PlexApiError::CassetteLockPoisoned)?;
+
+        let mut matching = interactions
+            .iter_mut()
+            .filter(|(i, _)| i.request.method == method && path_and_query(&i.request.url) == path)
+            .collect::<Vec<_>>();
+
+        let interaction = match matching.iter().position(|(_, used)| !used) {
+            Some(idx) => {
+                matching[idx].1 = true;
+                &matching[idx].0
+            }
+            None => match matching.last() {
+                Some((interaction, _)) => interaction,
+                None => return Err(PlexApiError::CassetteInteractionNotFound { method, url }),
+            },
+        };
+
+        interaction.response.to_response()
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Result<Response> {
+        let mut headers = HeaderMap::new();
+        for (key, value) in &self.headers {
+            headers.insert(HeaderName::from_str(key)?, HeaderValue::from_str(value)?);
+        }
+
+        Ok(Response::new(
+            StatusCode::from_u16(self.status)
+                .map_err(|_| PlexApiError::CassetteInvalidStatusCode(self.status))?,
+            headers,
+            self.body.clone().into_bytes(),
+        ))
+    }
+}
+
+fn path_and_query(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(url) => match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_owned(),
+        },
+        Err(_) => url.to_owned(),
+    }
+}
+
+fn scrub_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(key, value)| {
+            let value = if SECRET_HEADERS.contains(&key.as_str())
+                || IDENTITY_HEADERS.contains(&key.as_str())
+            {
+                REDACTED.to_owned()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (key.as_str().to_owned(), value)
+        })
+        .collect()
+}
+
+fn scrub_url(url: &Url) -> String {
+    let mut url = url.clone();
+    if let Some(query) = url.query() {
+        let query = scrub_params(query);
+        url.set_query(Some(&query));
+    }
+    url.to_string()
+}
+
+/// Replaces secret values in urlencoded parameters.
+fn scrub_params(params: &str) -> String {
+    let pairs = url::form_urlencoded::parse(params.as_bytes()).map(|(key, value)| {
+        if SECRET_PARAMS.contains(&key.as_ref()) {
+            (key, REDACTED.into())
+        } else {
+            (key, value)
+        }
+    });
+
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish()
+}
+
+/// Replaces values of secret JSON-keys and XML-attributes in a body.
+fn scrub_body(body: &str) -> String {
+    let mut body = body.to_owned();
+    for field in SECRET_FIELDS.iter() {
+        body = scrub_values(&body, &format!("\"{}\":", field), true);
+        body = scrub_values(&body, &format!("{}=", field), false);
+    }
+    body
+}
+
+fn scrub_values(body: &str, prefix: &str, allow_whitespace: bool) -> String {
+    let mut result = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(idx) = rest.find(prefix) {
+        // Skip the fields, which only end with the name, e.g. `myauthToken=`
+        let preceding = rest[..idx]
+            .chars()
+            .next_back()
+            .or_else(|| result.chars().next_back());
+        let (head, tail) = rest.split_at(idx + prefix.len());
+        result.push_str(head);
+        if preceding.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            rest = tail;
+            continue;
+        }
+
+        let value = if allow_whitespace {
+            tail.trim_start()
+        } else {
+            tail
+        };
+        result.push_str(&tail[..tail.len() - value.len()]);
+
+        match value
+            .strip_prefix('"')
+            .and_then(|value| value.find('"').map(|end| (value, end)))
+        {
+            Some((value, end)) => {
+                result.push('"');
+                result.push_str(REDACTED);
+                result.push('"');
+                rest = &value[end + 1..];
+            }
+            None => rest = value,
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
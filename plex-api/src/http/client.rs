@@ -1,140 +1,375 @@
-use std::sync::{LockResult, RwLock, RwLockReadGuard};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use reqwest::{header::HeaderMap, Client};
 use uuid::Uuid;
 
 use crate::config;
+use crate::http::{
+    Format, HttpTransport, Middleware, RateLimiter, RequestBuilder, ReqwestTransport,
+    ResponseCache, RetryPolicy,
+};
 use crate::Result;
 
-/// Sets custom HTTP-client, e.g. to change request timeout or to set a proxy.
+/// Client identifier for the clients, which don't set their own one. It's generated once per
+/// process, so all such clients are seen by Plex as a single device.
+static DEFAULT_CLIENT_IDENTIFIER: OnceLock<String> = OnceLock::new();
+
+fn default_client_identifier() -> &'static str {
+    DEFAULT_CLIENT_IDENTIFIER.get_or_init(|| {
+        warn!("Generating random identifier for the machine! Set client_identifier to avoid this");
+        Uuid::new_v4().to_string()
+    })
+}
+
+/// A configured client: HTTP-transport and a set of `X-Plex-*` headers, sent with every request.
+///
+/// [`MyPlexAccount`], [`Server`] and [`Device`] keep the client they were obtained with, so
+/// several clients with different identities can be used within a single process.
 ///
-/// Error would be returned if [`RwLock`] had been poisoned.
+/// [`MyPlexAccount`]: struct.MyPlexAccount.html
+/// [`Server`]: struct.Server.html
+/// [`Device`]: struct.Device.html
+#[derive(Debug, Clone)]
+pub struct PlexClient {
+    transport: Arc<dyn HttpTransport>,
+    headers: HeaderMap,
+    client_identifier: String,
+    my_plex_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    format: Format,
+}
+
+impl PlexClient {
+    /// Returns a builder to configure a new client.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Returns `X-Plex-Client-Identifier` used by the client.
+    pub fn get_client_identifier(&self) -> &str {
+        &self.client_identifier
+    }
+
+    /// Returns headers, which are sent with every request.
+    pub const fn get_headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns base url of MyPlex API, `https://plex.tv/` by default.
+    pub fn get_my_plex_url(&self) -> &str {
+        &self.my_plex_url
+    }
+
+    /// Returns the policy, used to retry failed requests.
+    pub const fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Returns a copy of the client, which uses the provided retry policy.
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Self {
+        PlexClient {
+            retry_policy,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the rate limiter, if it's set.
+    pub const fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Returns a copy of the client, which uses the provided rate limiter.
+    pub fn with_rate_limiter(&self, rate_limiter: RateLimiter) -> Self {
+        PlexClient {
+            rate_limiter: Some(rate_limiter),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the cache for responses to `GET` requests, if it's set.
+    pub fn get_response_cache(&self) -> Option<&dyn ResponseCache> {
+        self.response_cache.as_deref()
+    }
+
+    /// Returns a copy of the client, which additionally uses the provided middleware.
+    pub fn with_middleware<M: Middleware + 'static>(&self, middleware: M) -> Self {
+        let mut client = self.clone();
+        client.middlewares.push(Arc::new(middleware));
+        client
+    }
+
+    /// Returns the format, requested from the servers by default.
+    pub const fn get_format(&self) -> Format {
+        self.format
+    }
+
+    pub(crate) fn get_middlewares(&self) -> &[Arc<dyn Middleware>] {
+        &self.middlewares
+    }
+
+    pub(crate) fn get_transport(&self) -> &dyn HttpTransport {
+        self.transport.as_ref()
+    }
+
+    pub(crate) fn request(&self, method: reqwest::Method, url: &str) -> RequestBuilder {
+        RequestBuilder::new(self, method, url)
+    }
+}
+
+/// Builder for [`PlexClient`]. Every value which isn't set explicitly would be filled with
+/// a sensible default.
 ///
-/// [`RwLock`]: https://doc.rust-lang.org/std/sync/struct.RwLock.html
+/// [`PlexClient`]: struct.PlexClient.html
 ///
 /// # Examples
 ///
 /// ```
-/// use plex_api::set_http_client;
+/// use plex_api::ClientBuilder;
 /// use reqwest::{Client, Proxy};
 /// use std::time::Duration;
 ///
-/// set_http_client(Client::builder()
+/// let client = ClientBuilder::default()
+///     .product("My Plex App")
+///     .client_identifier("my-unique-device-id")
+///     .http_client(Client::builder()
 ///                     .timeout(Duration::from_secs(1))
 ///                     .proxy(Proxy::http("http://example.com").expect("Proxy failed"))
 ///                     .build()
-///                     .expect("Build failed")
-/// ).expect("Mutex poisoned");
+///                     .expect("Build failed"))
+///     .build()
+///     .expect("Client build failed");
 /// ```
-pub fn set_http_client(c: Client) -> Result<()> {
-    let mut client = config::HTTP_CLIENT.write()?;
-    *client = c;
-    Ok(())
-}
-
-pub fn get_http_client() -> LockResult<RwLockReadGuard<'static, Client>> {
-    // override http-client in tests, to prevent using cached connections across different tokio runtimes
-    #[cfg(test)]
-    {
-        debug!("Resetting HTTP_CLIENT to default value to prevent connections caching");
-        set_http_client(
-            Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .expect("Build failed"),
-        )
-        .expect("Failed to reset http-client");
-    }
-    config::HTTP_CLIENT.read()
+#[derive(Debug, Default, Clone)]
+pub struct ClientBuilder {
+    transport: Option<Arc<dyn HttpTransport>>,
+    provides: Option<String>,
+    platform: Option<String>,
+    platform_version: Option<String>,
+    product: Option<String>,
+    version: Option<String>,
+    device: Option<String>,
+    device_name: Option<String>,
+    sync_version: Option<String>,
+    client_identifier: Option<String>,
+    my_plex_url: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    format: Option<Format>,
 }
 
-lazy_static! {
-    static ref HEADERS_CACHE: RwLock<HeaderMap> = RwLock::new(HeaderMap::new());
-}
+impl ClientBuilder {
+    /// Sets custom HTTP-client, e.g. to change request timeout or to set a proxy.
+    pub fn http_client(self, client: Client) -> Self {
+        self.transport(ReqwestTransport::from(client))
+    }
 
-/// Resets cached headers
-///
-/// Useful when you need to change headers at the runtime.
-pub fn clear_headers_cache() -> Result<()> {
-    let mut cache = HEADERS_CACHE.write()?;
-    *cache = HeaderMap::new();
-    Ok(())
-}
+    /// Sets custom HTTP-transport, which would be used instead of the default one, e.g. to
+    /// respond with prepared data in tests.
+    pub fn transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
 
-pub fn base_headers() -> Result<HeaderMap> {
-    {
-        let cache = HEADERS_CACHE.read()?;
-        if !cache.is_empty() {
-            return Ok(cache.clone());
-        }
+    /// `X-Plex-Provides` header value.
+    ///
+    /// Should be one or more of `controller`, `server`, `sync-target`, `player`.
+    pub fn provides(mut self, provides: &str) -> Self {
+        self.provides = Some(provides.to_owned());
+        self
     }
 
-    let mut headers = HeaderMap::new();
+    /// `X-Plex-Platform` header value.
+    ///
+    /// Platform name, e.g. iOS, macOS, etc.
+    pub fn platform(mut self, platform: &str) -> Self {
+        self.platform = Some(platform.to_owned());
+        self
+    }
 
-    let sys_platform = sys_info::os_type().unwrap_or_else(|_| "unknown".into());
-    let sys_version = sys_info::os_release().unwrap_or_else(|_| "unknown".into());
-    let sys_hostname = sys_info::hostname().unwrap_or_else(|_| "unknown".into());
+    /// `X-Plex-Platform-Version` header value.
+    ///
+    /// OS version, e.g. 4.3.1
+    pub fn platform_version(mut self, platform_version: &str) -> Self {
+        self.platform_version = Some(platform_version.to_owned());
+        self
+    }
 
-    let provides = *config::X_PLEX_PROVIDES.read()?;
-    headers.insert("X-Plex-Provides", provides.parse()?);
+    /// `X-Plex-Product` header value.
+    ///
+    /// Application name, e.g. Laika, Plex Media Server, Media Link.
+    pub fn product(mut self, product: &str) -> Self {
+        self.product = Some(product.to_owned());
+        self
+    }
 
-    let mut product = *config::X_PLEX_PRODUCT.read()?;
-    if product.is_empty() {
-        product = config::PROJECT.unwrap_or("plex-api");
+    /// `X-Plex-Version` header value.
+    ///
+    /// Application version, e.g. 10.6.7.
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_owned());
+        self
     }
 
-    headers.insert("X-Plex-Product", product.parse()?);
+    /// `X-Plex-Device` header value.
+    ///
+    /// Device name and model number, e.g. iPhone3,2, Motorola XOOM™, LG5200TV.
+    pub fn device(mut self, device: &str) -> Self {
+        self.device = Some(device.to_owned());
+        self
+    }
 
-    let mut version = *config::X_PLEX_VERSION.read()?;
-    if version.is_empty() {
-        version = config::VERSION.unwrap_or("unknown");
+    /// `X-Plex-Device-Name` header value.
+    ///
+    /// Primary name for the device, e.g. "Plex Web (Chrome)".
+    pub fn device_name(mut self, device_name: &str) -> Self {
+        self.device_name = Some(device_name.to_owned());
+        self
     }
 
-    headers.insert("X-Plex-Version", version.parse()?);
-    headers.insert(
-        "X-Plex-Sync-Version",
-        config::X_PLEX_SYNC_VERSION.read()?.parse()?,
-    );
+    /// `X-Plex-Sync-Version` header value.
+    ///
+    /// The lib supports only "2", but you can set any value at your own risk.
+    pub fn sync_version(mut self, sync_version: &str) -> Self {
+        self.sync_version = Some(sync_version.to_owned());
+        self
+    }
 
-    let mut platform = *config::X_PLEX_PLATFORM.read()?;
-    if platform.is_empty() {
-        platform = &sys_platform;
+    /// `X-Plex-Client-Identifier` header value.
+    ///
+    /// UUID, serial number, or other number unique per device. Random value, shared by all
+    /// the clients within the process, would be generated if not set.
+    ///
+    /// **N.B.** Should be unique for each of your devices.
+    pub fn client_identifier(mut self, client_identifier: &str) -> Self {
+        self.client_identifier = Some(client_identifier.to_owned());
+        self
     }
 
-    headers.insert("X-Plex-Platform", platform.parse()?);
+    /// Base url of MyPlex API, `https://plex.tv/` if not set.
+    ///
+    /// Useful to communicate with a mock of MyPlex in tests.
+    pub fn my_plex_url(mut self, my_plex_url: &str) -> Self {
+        let mut my_plex_url = my_plex_url.to_owned();
+        if !my_plex_url.ends_with('/') {
+            my_plex_url.push('/');
+        }
+        self.my_plex_url = Some(my_plex_url);
+        self
+    }
 
-    let mut platform_version = *config::X_PLEX_PLATFORM_VERSION.read()?;
-    if platform_version.is_empty() {
-        platform_version = &sys_version;
+    /// Policy for retrying failed requests, see [`RetryPolicy`] for the defaults.
+    ///
+    /// [`RetryPolicy`]: struct.RetryPolicy.html
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
     }
 
-    headers.insert("X-Plex-Platform-Version", platform_version.parse()?);
+    /// Rate limiter, which delays requests exceeding configured limits. Requests are not
+    /// limited by default.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
 
-    let mut client_identifier: String = String::from(&*config::X_PLEX_CLIENT_IDENTIFIER.read()?);
-    if client_identifier.is_empty() {
-        warn!("Generating random identifier for the machine! Set X_PLEX_CLIENT_IDENTIFIER to avoid this");
-        let random_uuid = Uuid::new_v4();
-        client_identifier = random_uuid.to_string();
+    /// Cache for responses to `GET` requests. Stored responses are revalidated with
+    /// `If-None-Match` / `If-Modified-Since`, and are used without a request while they're fresh
+    /// according to `Cache-Control: max-age`. Responses are not cached by default.
+    pub fn response_cache<C: ResponseCache + 'static>(mut self, response_cache: C) -> Self {
+        self.response_cache = Some(Arc::new(response_cache));
+        self
     }
 
-    headers.insert("X-Plex-Client-Identifier", client_identifier.parse()?);
+    /// Adds a middleware to the chain, called around every request, see [`Middleware`].
+    ///
+    /// [`Middleware`]: trait.Middleware.html
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
 
-    let mut device = *config::X_PLEX_DEVICE.read()?;
-    if device.is_empty() {
-        device = platform
+    /// Format of the responses, requested with `Accept` header, JSON if not set.
+    ///
+    /// Responses are decoded according to their `Content-Type`, so this is only a preference.
+    /// Endpoints, which are known to support a single format, always request it.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
     }
 
-    headers.insert("X-Plex-Device", device.parse()?);
+    pub fn build(self) -> Result<PlexClient> {
+        let mut headers = HeaderMap::new();
 
-    let mut device_name = *config::X_PLEX_DEVICE_NAME.read()?;
-    if device_name.is_empty() {
-        device_name = &sys_hostname;
-    }
+        let sys_platform = sys_info::os_type().unwrap_or_else(|_| "unknown".into());
+        let sys_version = sys_info::os_release().unwrap_or_else(|_| "unknown".into());
+        let sys_hostname = sys_info::hostname().unwrap_or_else(|_| "unknown".into());
+
+        let provides = self.provides.as_deref().unwrap_or("controller");
+        headers.insert("X-Plex-Provides", provides.parse()?);
 
-    headers.insert("X-Plex-Device-Name", device_name.parse()?);
+        let product = self
+            .product
+            .as_deref()
+            .unwrap_or_else(|| config::PROJECT.unwrap_or("plex-api"));
+        headers.insert("X-Plex-Product", product.parse()?);
 
-    let mut cache = HEADERS_CACHE.write()?;
-    *cache = headers.clone();
+        let version = self
+            .version
+            .as_deref()
+            .unwrap_or_else(|| config::VERSION.unwrap_or("unknown"));
+        headers.insert("X-Plex-Version", version.parse()?);
 
-    Ok(headers)
+        let sync_version = self
+            .sync_version
+            .as_deref()
+            .unwrap_or(config::SUPPORTED_X_PLEX_SYNC_VERSION);
+        headers.insert("X-Plex-Sync-Version", sync_version.parse()?);
+
+        let platform = self.platform.as_deref().unwrap_or(&sys_platform);
+        headers.insert("X-Plex-Platform", platform.parse()?);
+
+        let platform_version = self.platform_version.as_deref().unwrap_or(&sys_version);
+        headers.insert("X-Plex-Platform-Version", platform_version.parse()?);
+
+        let client_identifier = self
+            .client_identifier
+            .unwrap_or_else(|| default_client_identifier().to_owned());
+        headers.insert("X-Plex-Client-Identifier", client_identifier.parse()?);
+
+        let device = self.device.as_deref().unwrap_or(platform);
+        headers.insert("X-Plex-Device", device.parse()?);
+
+        let device_name = self.device_name.as_deref().unwrap_or(&sys_hostname);
+        headers.insert("X-Plex-Device-Name", device_name.parse()?);
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::from(
+                Client::builder()
+                    .timeout(Duration::from_secs(30))
+                    .connect_timeout(Duration::from_secs(5))
+                    .build()?,
+            )),
+        };
+
+        Ok(PlexClient {
+            transport,
+            headers,
+            client_identifier,
+            my_plex_url: self
+                .my_plex_url
+                .unwrap_or_else(|| String::from(config::MYPLEX_URL)),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            rate_limiter: self.rate_limiter,
+            response_cache: self.response_cache,
+            middlewares: self.middlewares,
+            format: self.format.unwrap_or_default(),
+        })
+    }
 }
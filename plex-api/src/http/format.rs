@@ -0,0 +1,68 @@
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
+
+use crate::Result;
+
+const MEDIA_CONTAINER_KEY: &str = "MediaContainer";
+
+/// Format of the response body, which is requested with `Accept` header and reported by the
+/// server with `Content-Type`.
+///
+/// Plex Media Server responds with either of them to the same request, so the models are
+/// decoded the same way from both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Json,
+    Xml,
+}
+
+impl Format {
+    /// Returns the MIME-type, used in `Accept` header to request the format.
+    pub const fn get_mime_type(&self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Xml => "application/xml",
+        }
+    }
+
+    /// Detects the format by `Content-Type` header, falling back to the first meaningful
+    /// character of the body, because some endpoints don't report the type correctly.
+    pub(crate) fn detect(headers: &HeaderMap, body: &[u8]) -> Option<Self> {
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_ascii_lowercase);
+
+        match content_type {
+            Some(ct) if ct.contains("json") => Some(Format::Json),
+            Some(ct) if ct.contains("xml") => Some(Format::Xml),
+            _ => match body.iter().find(|b| !b.is_ascii_whitespace()) {
+                Some(b'{') | Some(b'[') => Some(Format::Json),
+                Some(b'<') => Some(Format::Xml),
+                _ => None,
+            },
+        }
+    }
+
+    /// Decodes the body. JSON documents wrapped into `{"MediaContainer": {...}}` are unwrapped,
+    /// so the same model is used as for the XML `<MediaContainer>` root element.
+    pub(crate) fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T> {
+        match self {
+            Format::Json => {
+                let mut value: serde_json::Value = serde_json::from_slice(body)?;
+                if let Some(obj) = value.as_object_mut() {
+                    if obj.len() == 1 {
+                        if let Some(container) = obj.remove(MEDIA_CONTAINER_KEY) {
+                            value = container;
+                        }
+                    }
+                }
+                serde_json::from_value(value).map_err(From::from)
+            }
+            Format::Xml => {
+                quick_xml::de::from_str(&String::from_utf8_lossy(body)).map_err(From::from)
+            }
+        }
+    }
+}
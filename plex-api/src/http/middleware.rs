@@ -0,0 +1,78 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::http::{Request, Response};
+use crate::PlexApiError;
+
+/// A hook, called by [`PlexClient`] around every attempt to send a request, e.g. to log the
+/// requests, to collect metrics or to add extra headers.
+///
+/// Middlewares are called in the order they were added to [`ClientBuilder`] before sending
+/// the request, and in the reverse order after receiving the response. Every retry attempt
+/// goes through the whole chain, responses served from the cache don't.
+///
+/// [`PlexClient`]: struct.PlexClient.html
+/// [`ClientBuilder`]: struct.ClientBuilder.html
+///
+/// # Examples
+///
+/// ```
+/// use plex_api::{ClientBuilder, Middleware, Request};
+///
+/// #[derive(Debug)]
+/// struct Language(&'static str);
+///
+/// impl Middleware for Language {
+///     fn on_request(&self, request: &mut Request) {
+///         request
+///             .headers_mut()
+///             .insert("X-Plex-Language", self.0.parse().unwrap());
+///     }
+/// }
+///
+/// let client = ClientBuilder::default()
+///     .client_identifier("my-unique-device-id")
+///     .middleware(Language("de"))
+///     .build()
+///     .expect("Client build failed");
+/// ```
+pub trait Middleware: Debug + Send + Sync {
+    /// Called before the request is sent.
+    fn on_request(&self, _request: &mut Request) {}
+
+    /// Called after the response was received, with the time spent waiting for it.
+    fn on_response(&self, _request: &Request, _response: &mut Response, _elapsed: Duration) {}
+
+    /// Called when the transport failed to perform the request.
+    fn on_error(&self, _request: &Request, _error: &PlexApiError, _elapsed: Duration) {}
+}
+
+/// [`Middleware`], which logs every request with `debug` level and failures with `warn`.
+/// Values of `X-Plex-Token` are never logged.
+///
+/// [`Middleware`]: trait.Middleware.html
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingMiddleware;
+
+impl Middleware for LoggingMiddleware {
+    fn on_response(&self, request: &Request, response: &mut Response, elapsed: Duration) {
+        debug!(
+            "{} {} -> {} ({} bytes in {:?})",
+            request.get_method(),
+            request.get_url().path(),
+            response.status(),
+            response.bytes().len(),
+            elapsed
+        );
+    }
+
+    fn on_error(&self, request: &Request, error: &PlexApiError, elapsed: Duration) {
+        warn!(
+            "{} {} failed after {:?}: {}",
+            request.get_method(),
+            request.get_url().path(),
+            elapsed,
+            error
+        );
+    }
+}
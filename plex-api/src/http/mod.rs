@@ -1,3 +1,17 @@
+mod cache;
+mod cassette;
 mod client;
+mod format;
+mod middleware;
+mod rate_limit;
+mod retry;
+mod transport;
 
+pub use cache::{CachedResponse, DiskCache, InMemoryCache, ResponseCache};
+pub use cassette::*;
 pub use client::*;
+pub use format::Format;
+pub use middleware::*;
+pub use rate_limit::*;
+pub use retry::*;
+pub use transport::*;
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Number of requests, allowed within a period of time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    per: Duration,
+}
+
+impl RateLimit {
+    /// Allows up to `requests` requests within `per`; the whole amount might be used at once,
+    /// after that requests are spread evenly.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        RateLimit {
+            requests: requests.max(1),
+            per,
+        }
+    }
+
+    /// Allows up to `requests` requests per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Allows up to `requests` requests per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        f64::from(self.requests) / self.per.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Client-side token-bucket rate limiter, which delays requests exceeding the configured
+/// limits. Limits are applied per host, every host has a separate bucket.
+///
+/// Clones of the limiter share the buckets, so the same limiter might be attached to several
+/// clients.
+///
+/// # Examples
+///
+/// ```
+/// use plex_api::{ClientBuilder, RateLimit, RateLimiter};
+///
+/// let client = ClientBuilder::default()
+///     .client_identifier("my-unique-device-id")
+///     .rate_limiter(RateLimiter::default()
+///                     .host_limit("plex.tv", RateLimit::per_minute(60)))
+///     .build()
+///     .expect("Client build failed");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    default_limit: Option<RateLimit>,
+    host_limits: HashMap<String, RateLimit>,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Limit for every host, which doesn't have its own limit.
+    pub fn limit(mut self, limit: RateLimit) -> Self {
+        self.default_limit = Some(limit);
+        self
+    }
+
+    /// Limit for the provided host, e.g. `plex.tv`.
+    pub fn host_limit(mut self, host: &str, limit: RateLimit) -> Self {
+        self.host_limits.insert(host.to_lowercase(), limit);
+        self
+    }
+
+    /// Returns the limit, applied to the provided host.
+    pub fn get_limit(&self, host: &str) -> Option<RateLimit> {
+        self.host_limits
+            .get(&host.to_lowercase())
+            .copied()
+            .or(self.default_limit)
+    }
+
+    /// Waits until a request to the url is allowed.
+    pub(crate) async fn acquire(&self, url: &Url) {
+        let delay = self.reserve(url);
+        if delay > Duration::from_secs(0) {
+            debug!("Rate limit reached for {}, waiting {:?}", url, delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Takes a token from the host's bucket and returns the time to wait before it's available.
+    /// The bucket goes below zero, when the token isn't available yet, so concurrent requests
+    /// queue up instead of competing for the same token.
+    fn reserve(&self, url: &Url) -> Duration {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let limit = match self.get_limit(&host) {
+            Some(limit) => limit,
+            None => return Duration::from_secs(0),
+        };
+
+        let capacity = f64::from(limit.requests);
+        let rate = limit.tokens_per_second();
+        let now = Instant::now();
+
+        let mut buckets = match self.buckets.lock() {
+            Ok(buckets) => buckets,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let bucket = buckets.entry(host).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity) - 1.0;
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
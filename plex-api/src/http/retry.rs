@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{header::RETRY_AFTER, Method, StatusCode};
+use std::time::Duration;
+
+use crate::http::Response;
+use crate::PlexApiError;
+
+/// Describes when and how [`PlexClient`] repeats failed requests.
+///
+/// Only idempotent requests (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`) are retried, on
+/// retryable statuses or on timeouts and connection errors. The delay between attempts grows
+/// exponentially and is randomized (jittered) to avoid retrying in lockstep with other clients.
+///
+/// The default policy makes up to 3 attempts, starting with 500ms delay, and retries on
+/// `429 Too Many Requests`, `502 Bad Gateway`, `503 Service Unavailable` and
+/// `504 Gateway Timeout`.
+///
+/// [`PlexClient`]: struct.PlexClient.html
+///
+/// # Examples
+///
+/// ```
+/// use plex_api::{ClientBuilder, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let client = ClientBuilder::default()
+///     .client_identifier("my-unique-device-id")
+///     .retry_policy(RetryPolicy::default()
+///                     .max_attempts(5)
+///                     .initial_delay(Duration::from_secs(1)))
+///     .build()
+///     .expect("Client build failed");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    jitter: bool,
+    statuses: Vec<StatusCode>,
+    retry_on_timeout: bool,
+    retry_on_connect: bool,
+    respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_on_timeout: true,
+            retry_on_connect: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a policy, which makes exactly one attempt.
+    pub fn none() -> Self {
+        RetryPolicy::default().max_attempts(1)
+    }
+
+    /// Total number of attempts, including the first one. Values below 1 are treated as 1.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the second attempt.
+    pub const fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Upper bound for any delay, including the one requested with `Retry-After`.
+    pub const fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Factor, by which the delay grows after every failed attempt.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Randomize every delay between a half and the full computed value.
+    pub const fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Response statuses, which should be retried.
+    pub fn statuses(mut self, statuses: &[StatusCode]) -> Self {
+        self.statuses = statuses.to_vec();
+        self
+    }
+
+    /// Retry requests, which failed with a timeout.
+    pub const fn retry_on_timeout(mut self, retry_on_timeout: bool) -> Self {
+        self.retry_on_timeout = retry_on_timeout;
+        self
+    }
+
+    /// Retry requests, which failed to connect to the host.
+    pub const fn retry_on_connect(mut self, retry_on_connect: bool) -> Self {
+        self.retry_on_connect = retry_on_connect;
+        self
+    }
+
+    /// Wait for the time, requested by the server in `Retry-After` header.
+    pub const fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    pub const fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub(crate) fn is_idempotent(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        )
+    }
+
+    pub(crate) fn should_retry_response(&self, response: &Response) -> bool {
+        self.statuses.contains(&response.status())
+    }
+
+    pub(crate) fn should_retry_error(&self, error: &PlexApiError) -> bool {
+        match error {
+            PlexApiError::ReqwestError { source } => {
+                (self.retry_on_timeout && source.is_timeout())
+                    || (self.retry_on_connect && source.is_connect())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns a delay before the next attempt, after `attempt` attempts were made.
+    pub(crate) fn delay(&self, attempt: u32, response: Option<&Response>) -> Duration {
+        if self.respect_retry_after {
+            if let Some(delay) = response.and_then(retry_after) {
+                return delay.min(self.max_delay);
+            }
+        }
+
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        let delay = delay.min(self.max_delay.as_secs_f64());
+        let delay = if self.jitter && delay > 0.0 {
+            rand::thread_rng().gen_range(delay / 2.0..=delay)
+        } else {
+            delay
+        };
+
+        Duration::from_secs_f64(delay)
+    }
+}
+
+/// Parses `Retry-After` header, which might be either a number of seconds or an HTTP-date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
@@ -0,0 +1,378 @@
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, IntoHeaderName};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::time::Instant;
+use url::Url;
+
+use crate::http::{cache, Format, RetryPolicy};
+use crate::{PlexApiError, PlexClient, Result};
+
+/// HTTP-transport, which is used by [`PlexClient`] to perform the requests.
+///
+/// The default implementation is [`ReqwestTransport`]; provide your own implementation to
+/// [`ClientBuilder::transport`], e.g. to serve canned responses in tests.
+///
+/// [`PlexClient`]: struct.PlexClient.html
+/// [`ReqwestTransport`]: struct.ReqwestTransport.html
+/// [`ClientBuilder::transport`]: struct.ClientBuilder.html#method.transport
+#[async_trait]
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Sends the request and returns the response with the whole body loaded. Non-success
+    /// statuses must be returned as a `Response`, `Err` is reserved for failures of the transport
+    /// itself, e.g. network errors.
+    async fn execute(&self, request: Request) -> Result<Response>;
+}
+
+/// A request, prepared to be sent by [`HttpTransport`].
+///
+/// [`HttpTransport`]: trait.HttpTransport.html
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: Method,
+    url: Url,
+    headers: HeaderMap,
+    body: Option<Vec<u8>>,
+}
+
+impl Request {
+    /// Creates a request without headers and body.
+    pub fn new(method: Method, url: Url) -> Self {
+        Request {
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// Returns HTTP-method of the request.
+    pub const fn get_method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Returns full URL of the request, including the query.
+    pub const fn get_url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns headers of the request, including `X-Plex-*` ones set by the client.
+    pub const fn get_headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns mutable headers of the request, e.g. to add a header before sending it.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Returns body of the request, if there is any.
+    pub fn get_body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+}
+
+/// A response, received by [`HttpTransport`].
+///
+/// [`HttpTransport`]: trait.HttpTransport.html
+#[derive(Debug, Clone)]
+pub struct Response {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    url: Option<Url>,
+}
+
+impl Response {
+    /// Creates a response, e.g. to be returned by a custom [`HttpTransport`].
+    ///
+    /// [`HttpTransport`]: trait.HttpTransport.html
+    pub fn new(status: StatusCode, headers: HeaderMap, body: Vec<u8>) -> Self {
+        Response {
+            status,
+            headers,
+            body,
+            url: None,
+        }
+    }
+
+    /// Returns URL of the request, the response was received for. It's set by [`PlexClient`]
+    /// for every response it returns.
+    ///
+    /// [`PlexClient`]: struct.PlexClient.html
+    pub const fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    /// Returns status code of the response.
+    pub const fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns headers of the response.
+    pub const fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns mutable headers of the response.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Returns raw body of the response.
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns body of the response as a string, invalid UTF-8 sequences are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Deserializes body of the response from JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_slice(&self.body).map_err(From::from)
+    }
+
+    /// Returns format of the body, detected by `Content-Type` or by the body itself.
+    pub fn format(&self) -> Option<Format> {
+        Format::detect(&self.headers, &self.body)
+    }
+
+    /// Deserializes body of a successful response from JSON or XML, according to its
+    /// [`format`]. An unsuccessful response is returned as an error, matching its status, see
+    /// [`PlexApiError::get_status_error`].
+    ///
+    /// [`format`]: #method.format
+    /// [`PlexApiError::get_status_error`]: enum.PlexApiError.html#method.get_status_error
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T> {
+        if !self.status.is_success() {
+            return Err(PlexApiError::from_response(self));
+        }
+
+        self.format().unwrap_or_default().decode(&self.body)
+    }
+}
+
+/// The default [`HttpTransport`], backed by [`reqwest::Client`].
+///
+/// [`HttpTransport`]: trait.HttpTransport.html
+/// [`reqwest::Client`]: https://docs.rs/reqwest/0.11/reqwest/struct.Client.html
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let mut rb = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            rb = rb.body(body);
+        }
+
+        let response = rb.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(Response::new(status, headers, body))
+    }
+}
+
+/// Builds a [`Request`] and sends it through the client's transport.
+///
+/// [`Request`]: struct.Request.html
+pub(crate) struct RequestBuilder {
+    client: PlexClient,
+    request: Result<Request>,
+}
+
+impl RequestBuilder {
+    pub(crate) fn new(client: &PlexClient, method: Method, url: &str) -> Self {
+        let request = Url::parse(url)
+            .map(|url| Request::new(method, url))
+            .map_err(PlexApiError::from)
+            .map(|mut request| {
+                request.headers = client.get_headers().clone();
+                request
+            });
+
+        RequestBuilder {
+            client: client.clone(),
+            request,
+        }
+    }
+
+    pub(crate) fn header<K: IntoHeaderName>(mut self, key: K, value: &str) -> Self {
+        if let Ok(ref mut request) = self.request {
+            match HeaderValue::from_str(value) {
+                Ok(value) => {
+                    request.headers.insert(key, value);
+                }
+                Err(e) => self.request = Err(PlexApiError::from(e)),
+            }
+        }
+        self
+    }
+
+    /// Requests the response in the provided format, overriding the client's preference.
+    pub(crate) fn accept(self, format: Format) -> Self {
+        self.header(reqwest::header::ACCEPT, format.get_mime_type())
+    }
+
+    pub(crate) fn headers(mut self, headers: HeaderMap) -> Self {
+        if let Ok(ref mut request) = self.request {
+            for (key, value) in headers.iter() {
+                request.headers.insert(key, value.clone());
+            }
+        }
+        self
+    }
+
+    pub(crate) fn form<T: serde::Serialize + ?Sized>(mut self, params: &T) -> Self {
+        if let Ok(ref mut request) = self.request {
+            match serde_urlencoded::to_string(params) {
+                Ok(body) => {
+                    request.headers.insert(
+                        reqwest::header::CONTENT_TYPE,
+                        HeaderValue::from_static("application/x-www-form-urlencoded"),
+                    );
+                    request.body = Some(body.into_bytes());
+                }
+                Err(e) => self.request = Err(PlexApiError::from(e)),
+            }
+        }
+        self
+    }
+
+    pub(crate) fn json<T: serde::Serialize + ?Sized>(mut self, body: &T) -> Self {
+        if let Ok(ref mut request) = self.request {
+            match serde_json::to_vec(body) {
+                Ok(body) => {
+                    request.headers.insert(
+                        reqwest::header::CONTENT_TYPE,
+                        HeaderValue::from_static("application/json"),
+                    );
+                    request.body = Some(body);
+                }
+                Err(e) => self.request = Err(PlexApiError::from(e)),
+            }
+        }
+        self
+    }
+
+    /// Sends the request, repeating it according to the client's [`RetryPolicy`]. `GET`
+    /// requests are served from the client's [`ResponseCache`] when possible.
+    ///
+    /// [`RetryPolicy`]: struct.RetryPolicy.html
+    /// [`ResponseCache`]: trait.ResponseCache.html
+    pub(crate) async fn send(self) -> Result<Response> {
+        let request = self.request?;
+        let url = request.get_url().clone();
+        let mut response = Self::send_cached(&self.client, request).await?;
+        if response.url.is_none() {
+            response.url = Some(url);
+        }
+        Ok(response)
+    }
+
+    async fn send_cached(client: &PlexClient, mut request: Request) -> Result<Response> {
+        let cache = match client.get_response_cache() {
+            Some(cache) if request.get_method() == Method::GET => cache,
+            _ => return Self::send_with_retries(client, request).await,
+        };
+
+        let key = cache::cache_key(&request);
+        let cached = cache.get(&key);
+        if let Some(ref cached) = cached {
+            if let Some(response) = cache::fresh_response(cached) {
+                debug!("Using cached response for {}", request.get_url());
+                return Ok(response);
+            }
+            cache::add_conditional_headers(&mut request, cached);
+        }
+
+        let mut response = Self::send_with_retries(client, request.clone()).await?;
+        if response.status() == StatusCode::NOT_MODIFIED && cached.is_none() {
+            // There is nothing to serve for `304`, e.g. the entry was evicted in the meantime
+            // or the conditional headers were set by the caller, so the full response is needed
+            debug!(
+                "Unexpected 304 for {}, requesting it again",
+                request.get_url()
+            );
+            cache.remove(&key);
+            cache::remove_conditional_headers(&mut request);
+            response = Self::send_with_retries(client, request).await?;
+        }
+        Ok(cache::handle_response(cache, &key, cached, response))
+    }
+
+    async fn send_with_retries(client: &PlexClient, request: Request) -> Result<Response> {
+        let policy = client.get_retry_policy();
+        let max_attempts = if RetryPolicy::is_idempotent(request.get_method()) {
+            policy.get_max_attempts()
+        } else {
+            1
+        };
+
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = client.get_rate_limiter() {
+                rate_limiter.acquire(request.get_url()).await;
+            }
+
+            let mut attempt_request = request.clone();
+            for middleware in client.get_middlewares() {
+                middleware.on_request(&mut attempt_request);
+            }
+
+            let started = Instant::now();
+            let mut result = client
+                .get_transport()
+                .execute(attempt_request.clone())
+                .await;
+            let elapsed = started.elapsed();
+            for middleware in client.get_middlewares().iter().rev() {
+                match result {
+                    Ok(ref mut response) => {
+                        middleware.on_response(&attempt_request, response, elapsed)
+                    }
+                    Err(ref e) => middleware.on_error(&attempt_request, e, elapsed),
+                }
+            }
+
+            let retry = attempt < max_attempts
+                && match &result {
+                    Ok(response) => policy.should_retry_response(response),
+                    Err(e) => policy.should_retry_error(e),
+                };
+            if !retry {
+                return result;
+            }
+
+            let delay = policy.delay(attempt, result.as_ref().ok());
+            warn!(
+                "Attempt {}/{} of {} {} failed, retrying in {:?}",
+                attempt,
+                max_attempts,
+                request.get_method(),
+                request.get_url(),
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
@@ -1,7 +1,5 @@
 extern crate chrono;
 #[macro_use]
-extern crate lazy_static;
-#[macro_use]
 extern crate log;
 #[macro_use]
 extern crate serde;
@@ -14,17 +12,20 @@ use reqwest::header::HeaderMap;
 
 use async_trait::async_trait;
 
-pub use self::config::{
-    X_PLEX_CLIENT_IDENTIFIER, X_PLEX_DEVICE, X_PLEX_DEVICE_NAME, X_PLEX_PLATFORM,
-    X_PLEX_PLATFORM_VERSION, X_PLEX_PRODUCT, X_PLEX_PROVIDES, X_PLEX_SYNC_VERSION, X_PLEX_VERSION,
-};
 pub use self::error::*;
-pub use self::http::{clear_headers_cache, set_http_client};
+pub use self::http::{
+    CachedResponse, Cassette, ClientBuilder, DiskCache, Format, HttpTransport, InMemoryCache,
+    LoggingMiddleware, Middleware, PlexClient, RateLimit, RateLimiter, RecordingTransport,
+    ReplayTransport, Request, ReqwestTransport, Response, ResponseCache, RetryPolicy,
+};
+pub use self::library::{Library, MetadataStream, Pagination};
 pub use self::media_container::*;
 pub use self::my_plex::*;
 pub use self::server::*;
 use url::Url;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod config;
 mod error;
 mod http;
@@ -46,7 +47,7 @@ pub type Result<T> = std::result::Result<T, crate::error::PlexApiError>;
 
 #[async_trait]
 trait CanBeDeleted {
-    async fn delete(&mut self) -> Result<reqwest::Response>;
+    async fn delete(&mut self) -> Result<Response>;
 }
 
 trait HasDeleteUrl {
@@ -56,14 +57,13 @@ trait HasDeleteUrl {
 #[async_trait]
 impl<T: HasDeleteUrl + CanMakeRequests + Send + Sync> CanBeDeleted for T {
     /// Remove current object from your account / server.
-    async fn delete(&mut self) -> Result<reqwest::Response> {
+    async fn delete(&mut self) -> Result<Response> {
         let url = self.get_delete_url();
 
         if let Some(url) = url {
             self.prepare_query(&url, reqwest::Method::DELETE)?
                 .send()
                 .await
-                .map_err(From::from)
         } else {
             Err(PlexApiError::DeleteUrlIsNotProvided)
         }
@@ -78,12 +78,20 @@ trait HasBaseUrl {
     fn get_base_url(&self) -> &str;
 }
 
+trait HasPlexClient {
+    fn get_client(&self) -> Option<&PlexClient>;
+
+    fn require_client(&self) -> Result<&PlexClient> {
+        self.get_client().ok_or(PlexApiError::ClientIsNotSet)
+    }
+}
+
 trait CanMakeRequests {
-    fn prepare_query<P: reqwest::IntoUrl + AsStr>(
+    fn prepare_query<P: AsStr>(
         &self,
         url: P,
         method: reqwest::Method,
-    ) -> Result<reqwest::RequestBuilder>;
+    ) -> Result<http::RequestBuilder>;
 }
 
 pub trait AsStr {
@@ -108,12 +116,12 @@ impl AsStr for &String {
     }
 }
 
-impl<T: HasPlexHeaders + HasBaseUrl> CanMakeRequests for T {
-    fn prepare_query<P: reqwest::IntoUrl + AsStr>(
+impl<T: HasPlexHeaders + HasBaseUrl + HasPlexClient> CanMakeRequests for T {
+    fn prepare_query<P: AsStr>(
         &self,
         url: P,
         method: reqwest::Method,
-    ) -> Result<reqwest::RequestBuilder> {
+    ) -> Result<http::RequestBuilder> {
         let request_url = {
             let s = url.as_str();
             match url::Url::parse(s) {
@@ -132,62 +140,53 @@ impl<T: HasPlexHeaders + HasBaseUrl> CanMakeRequests for T {
             }
         };
 
-        let client = http::get_http_client()?;
-
-        Ok(client
-            .request(method, request_url?)
+        Ok(self
+            .require_client()?
+            .request(method, request_url?.as_str())
             .headers(self.headers()?))
     }
 }
 
 #[async_trait]
 trait InternalHttpApi {
-    async fn get<U: reqwest::IntoUrl + AsStr + Send>(
-        &self,
-        url: U,
-    ) -> crate::Result<reqwest::Response>;
-    async fn post_form<U: reqwest::IntoUrl + AsStr + Send, T: serde::Serialize + ?Sized + Sync>(
+    async fn get<U: AsStr + Send>(&self, url: U) -> crate::Result<Response>;
+    async fn post_form<U: AsStr + Send, T: serde::Serialize + ?Sized + Sync>(
         &self,
         url: U,
         params: &T,
-    ) -> crate::Result<reqwest::Response>;
-    async fn put_form<U: reqwest::IntoUrl + AsStr + Send, T: serde::Serialize + ?Sized + Sync>(
+    ) -> crate::Result<Response>;
+    async fn put_form<U: AsStr + Send, T: serde::Serialize + ?Sized + Sync>(
         &self,
         url: U,
         params: &T,
-    ) -> crate::Result<reqwest::Response>;
+    ) -> crate::Result<Response>;
 }
 
 #[async_trait]
 impl<T: CanMakeRequests + Sync> InternalHttpApi for T {
-    async fn get<U: reqwest::IntoUrl + AsStr + Send>(&self, url: U) -> Result<reqwest::Response> {
-        self.prepare_query(url, reqwest::Method::GET)?
-            .send()
-            .await
-            .map_err(From::from)
+    async fn get<U: AsStr + Send>(&self, url: U) -> Result<Response> {
+        self.prepare_query(url, reqwest::Method::GET)?.send().await
     }
 
-    async fn post_form<U: reqwest::IntoUrl + AsStr + Send, P: serde::Serialize + ?Sized + Sync>(
+    async fn post_form<U: AsStr + Send, P: serde::Serialize + ?Sized + Sync>(
         &self,
         url: U,
         params: &P,
-    ) -> Result<reqwest::Response> {
+    ) -> Result<Response> {
         self.prepare_query(url, reqwest::Method::POST)?
             .form(params)
             .send()
             .await
-            .map_err(From::from)
     }
 
-    async fn put_form<U: reqwest::IntoUrl + AsStr + Send, P: serde::Serialize + ?Sized + Sync>(
+    async fn put_form<U: AsStr + Send, P: serde::Serialize + ?Sized + Sync>(
         &self,
         url: U,
         params: &P,
-    ) -> Result<reqwest::Response> {
-        self.prepare_query(url, reqwest::Method::POST)?
+    ) -> Result<Response> {
+        self.prepare_query(url, reqwest::Method::PUT)?
             .form(params)
             .send()
             .await
-            .map_err(From::from)
     }
 }
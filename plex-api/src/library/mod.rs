@@ -1,40 +1,64 @@
+mod stream;
+
+pub use self::stream::{MetadataStream, Pagination};
+
 use crate::{DirectoryMediaContainer, Result};
-use crate::{
-    InternalHttpApi, LibraryMediaContainer, LibraryMediaContainerOuter, PlexApiError, Server,
-};
+use crate::{InternalHttpApi, LibraryMediaContainer, Server};
 
-#[derive(Debug)]
-pub struct Library<'a> {
-    server: &'a Server,
+/// A piece of the library, e.g. the sections. It owns a clone of the [`Server`], so it might be
+/// stored or moved to another task independently from the server.
+///
+/// [`Server`]: struct.Server.html
+#[derive(Debug, Clone)]
+pub struct Library {
+    server: Server,
     info: LibraryMediaContainer,
 }
 
 const LIBRARY_SECTIONS_URL: &str = "library/sections";
-const LIBRARY_RECENTLY_ADDED_URL: &str = "library/recentlyAdded";
+pub(crate) const LIBRARY_RECENTLY_ADDED_URL: &str = "library/recentlyAdded";
 const LIBRARY_ON_DECK_URL: &str = "library/onDeck";
 
-impl<'a> Library<'a> {
-    pub(crate) async fn sections(server: &'a Server) -> Result<Library<'a>> {
+impl Library {
+    pub(crate) async fn sections(server: &Server) -> Result<Library> {
         Self::load(server, LIBRARY_SECTIONS_URL).await
     }
 
-    pub(crate) async fn recently_added(server: &'a Server) -> Result<Library<'a>> {
+    pub(crate) async fn recently_added(server: &Server) -> Result<Library> {
         Self::load(server, LIBRARY_RECENTLY_ADDED_URL).await
     }
 
-    pub(crate) async fn on_deck(server: &'a Server) -> Result<Library<'a>> {
+    pub(crate) async fn on_deck(server: &Server) -> Result<Library> {
         Self::load(server, LIBRARY_ON_DECK_URL).await
     }
 
-    async fn load(server: &'a Server, url: &str) -> Result<Library<'a>> {
-        let response = server.get(url).await?;
-        if response.status() == reqwest::StatusCode::OK {
-            let mc =
-                LibraryMediaContainer::from(response.json::<LibraryMediaContainerOuter>().await?);
-            Ok(Library { server, info: mc })
-        } else {
-            Err(PlexApiError::UnexpectedApiResponse(response.text().await?))
-        }
+    pub(crate) fn stream(server: &Server, url: &str, pagination: Pagination) -> MetadataStream {
+        stream::metadata_stream(server, url, pagination)
+    }
+
+    async fn load(server: &Server, url: &str) -> Result<Library> {
+        let info: LibraryMediaContainer = server.get(url).await?.decode()?;
+        Ok(Library {
+            server: server.clone(),
+            info,
+        })
+    }
+
+    /// Returns the server, the library was loaded from.
+    pub const fn get_server(&self) -> &Server {
+        &self.server
+    }
+
+    /// Returns a stream of the section's items, loaded page by page.
+    pub fn stream_section_items(
+        &self,
+        section: &DirectoryMediaContainer,
+        pagination: Pagination,
+    ) -> MetadataStream {
+        self.server.stream_metadata(
+            &format!("{}/{}/all", LIBRARY_SECTIONS_URL, section.key),
+            pagination,
+        )
     }
 
     pub const fn get_directory(&self) -> &Option<Vec<DirectoryMediaContainer>> {
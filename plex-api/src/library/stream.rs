@@ -0,0 +1,139 @@
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use crate::{CanMakeRequests, LibraryMediaContainer, MediaMetadata, Result, Server};
+
+const CONTAINER_START_HEADER: &str = "X-Plex-Container-Start";
+const CONTAINER_SIZE_HEADER: &str = "X-Plex-Container-Size";
+
+/// A stream of items, loaded page by page.
+pub type MetadataStream = BoxStream<'static, Result<MediaMetadata>>;
+
+/// Describes how the items are loaded by [`MetadataStream`].
+///
+/// By default items are requested by 100 per page and the next page is requested only after
+/// the previous one was consumed.
+///
+/// [`MetadataStream`]: type.MetadataStream.html
+///
+/// # Examples
+///
+/// ```
+/// use plex_api::Pagination;
+///
+/// // Keep up to 2 pages of 500 items loading, while the current one is processed
+/// let pagination = Pagination::default().page_size(500).prefetch(2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pagination {
+    page_size: u32,
+    prefetch: usize,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination {
+            page_size: 100,
+            prefetch: 0,
+        }
+    }
+}
+
+impl Pagination {
+    /// Number of items, requested at once. Values below 1 are treated as 1.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Number of the next pages, requested concurrently with the current one. Prefetching
+    /// requires `totalSize` to be reported by the server, otherwise pages are loaded one by one.
+    pub const fn prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    pub const fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    pub const fn get_prefetch(&self) -> usize {
+        self.prefetch
+    }
+}
+
+/// Loads a single page of the container.
+async fn load_page(
+    server: Server,
+    path: String,
+    start: u32,
+    size: u32,
+) -> Result<LibraryMediaContainer> {
+    server
+        .prepare_query(path.as_str(), reqwest::Method::GET)?
+        .header(CONTAINER_START_HEADER, &start.to_string())
+        .header(CONTAINER_SIZE_HEADER, &size.to_string())
+        .send()
+        .await?
+        .decode()
+}
+
+fn items(page: LibraryMediaContainer) -> BoxStream<'static, Result<MediaMetadata>> {
+    stream::iter(page.into_metadata().into_iter().map(Ok)).boxed()
+}
+
+/// Returns the stream of the container's items. The first page is used to get the total number
+/// of items, the rest of them are requested concurrently, according to the prefetch setting.
+pub(crate) fn metadata_stream(
+    server: &Server,
+    path: &str,
+    pagination: Pagination,
+) -> MetadataStream {
+    let server = server.clone();
+    let path = path.to_owned();
+    let page_size = pagination.page_size;
+
+    stream::once(load_page(server.clone(), path.clone(), 0, page_size))
+        .map_ok(move |first| {
+            let server = server.clone();
+            let path = path.clone();
+            let first_len = first.get_metadata().as_ref().map_or(0, Vec::len) as u32;
+            let total_size = first.get_media_container().get_total_size();
+            let first_items = items(first);
+
+            let rest = match total_size {
+                Some(total_size) => {
+                    stream::iter((page_size..total_size).step_by(page_size as usize))
+                        .map(move |start| load_page(server.clone(), path.clone(), start, page_size))
+                        .buffered(pagination.prefetch + 1)
+                        .map_ok(items)
+                        .try_flatten()
+                        .boxed()
+                }
+                None if first_len < page_size => stream::empty().boxed(),
+                None => stream::try_unfold(Some(page_size), move |start| {
+                    let server = server.clone();
+                    let path = path.clone();
+                    async move {
+                        let start = match start {
+                            Some(start) => start,
+                            None => return Result::Ok(None),
+                        };
+                        let page = load_page(server, path, start, page_size).await?;
+                        let len = page.get_metadata().as_ref().map_or(0, Vec::len) as u32;
+                        let next = if len < page_size {
+                            None
+                        } else {
+                            Some(start + page_size)
+                        };
+                        Ok(Some((items(page), next)))
+                    }
+                })
+                .try_flatten()
+                .boxed(),
+            };
+
+            first_items.chain(rest)
+        })
+        .try_flatten()
+        .boxed()
+}
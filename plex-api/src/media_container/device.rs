@@ -3,12 +3,16 @@ use serde_with::CommaSeparator;
 
 use crate::serde_helpers::option_bool_from_anything;
 use crate::server::Server;
-use crate::{HasBaseUrl, HasDeleteUrl, HasMyPlexToken, MediaContainer, PlexApiError};
+use crate::{
+    config, HasBaseUrl, HasDeleteUrl, HasMyPlexToken, HasPlexClient, MediaContainer, PlexApiError,
+    PlexClient,
+};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::net::IpAddr;
 use url::Url;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct DevicesMediaContainer {
@@ -16,6 +20,8 @@ pub struct DevicesMediaContainer {
     devices: Vec<Device>,
     #[serde(flatten)]
     media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
 impl DevicesMediaContainer {
@@ -25,9 +31,13 @@ impl DevicesMediaContainer {
     pub const fn get_devices(&self) -> &Vec<Device> {
         &self.devices
     }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Device {
@@ -40,9 +50,7 @@ pub struct Device {
     device: String,
     model: Option<String>,
     vendor: Option<String>,
-    #[serde(
-        deserialize_with = "serde_with::rust::StringWithSeparator::<CommaSeparator>::deserialize"
-    )]
+    #[serde(with = "serde_with::rust::StringWithSeparator::<CommaSeparator>")]
     provides: Vec<String>,
     client_identifier: String,
     version: Option<String>,
@@ -54,28 +62,53 @@ pub struct Device {
     #[serde(with = "chrono::serde::ts_seconds")]
     last_seen_at: DateTime<Utc>,
     #[serde(
-        deserialize_with = "serde_with::rust::StringWithSeparator::<CommaSeparator>::deserialize",
+        with = "serde_with::rust::StringWithSeparator::<CommaSeparator>",
         default
     )]
     screen_resolution: Vec<String>,
     #[serde(
         deserialize_with = "serde_with::rust::string_empty_as_none::deserialize",
+        serialize_with = "crate::serde_helpers::serialize_option_to_string",
         default
     )]
     screen_density: Option<u16>,
     #[serde(rename = "Connection")]
     connections: Option<Vec<Connection>>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     https_required: Option<bool>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     synced: Option<bool>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     relay: Option<bool>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     public_address_matches: Option<bool>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     presence: Option<bool>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     owned: Option<bool>,
     #[serde(rename = "SyncList")]
     sync_list: Option<SyncList>,
@@ -83,28 +116,77 @@ pub struct Device {
     auth_token: String,
     dns_rebinding_protection: Option<bool>,
     nat_loopback_supported: Option<bool>,
+    #[serde(skip)]
+    client: Option<PlexClient>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
-struct SyncList {
+pub struct SyncList {
     items_complete_count: u32,
     total_size: u64,
     version: u32,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl SyncList {
+    pub const fn get_items_complete_count(&self) -> u32 {
+        self.items_complete_count
+    }
+    pub const fn get_total_size(&self) -> u64 {
+        self.total_size
+    }
+    pub const fn get_version(&self) -> u32 {
+        self.version
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-struct Connection {
+pub struct Connection {
     uri: Url,
     protocol: Option<String>,
     address: Option<IpAddr>,
     port: Option<u32>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     local: Option<bool>,
-    #[serde(deserialize_with = "option_bool_from_anything", default)]
+    #[serde(
+        deserialize_with = "option_bool_from_anything",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     relay: Option<bool>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl Connection {
+    pub const fn get_uri(&self) -> &Url {
+        &self.uri
+    }
+    pub const fn get_protocol(&self) -> &Option<String> {
+        &self.protocol
+    }
+    pub const fn get_address(&self) -> &Option<IpAddr> {
+        &self.address
+    }
+    pub const fn get_port(&self) -> Option<u32> {
+        self.port
+    }
+    pub const fn get_local(&self) -> Option<bool> {
+        self.local
+    }
+    pub const fn get_relay(&self) -> Option<bool> {
+        self.relay
+    }
 }
 
 impl Device {
@@ -120,6 +202,7 @@ impl Device {
             return Err(PlexApiError::EmptyConnectionsList);
         }
 
+        let client = self.require_client()?;
         let mut connections = self.connections.clone().unwrap();
 
         connections.sort_by(|a, b| a.local.cmp(&b.local));
@@ -127,7 +210,7 @@ impl Device {
         let mut errors = HashMap::new();
 
         for c in connections {
-            let srv = Server::connect(c.uri.clone(), &self.auth_token).await;
+            let srv = Server::connect_with_client(client, c.uri.clone(), &self.auth_token).await;
 
             if srv.is_ok() {
                 return srv;
@@ -142,6 +225,98 @@ impl Device {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    pub(crate) fn set_client(&mut self, client: Option<&PlexClient>) {
+        self.client = client.cloned();
+    }
+
+    pub fn get_public_address(&self) -> &str {
+        &self.public_address
+    }
+    pub fn get_product(&self) -> &str {
+        &self.product
+    }
+    pub fn get_product_version(&self) -> &str {
+        &self.product_version
+    }
+    pub fn get_platform(&self) -> &str {
+        &self.platform
+    }
+    pub fn get_platform_version(&self) -> &str {
+        &self.platform_version
+    }
+    pub fn get_device(&self) -> &str {
+        &self.device
+    }
+    pub const fn get_model(&self) -> &Option<String> {
+        &self.model
+    }
+    pub const fn get_vendor(&self) -> &Option<String> {
+        &self.vendor
+    }
+    pub const fn get_provides(&self) -> &Vec<String> {
+        &self.provides
+    }
+    pub fn get_client_identifier(&self) -> &str {
+        &self.client_identifier
+    }
+    pub const fn get_version(&self) -> &Option<String> {
+        &self.version
+    }
+    pub const fn get_id(&self) -> Option<u32> {
+        self.id
+    }
+    pub const fn get_token(&self) -> &Option<String> {
+        &self.token
+    }
+    pub const fn get_access_token(&self) -> &Option<String> {
+        &self.access_token
+    }
+    pub const fn get_created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+    pub const fn get_last_seen_at(&self) -> DateTime<Utc> {
+        self.last_seen_at
+    }
+    pub const fn get_screen_resolution(&self) -> &Vec<String> {
+        &self.screen_resolution
+    }
+    pub const fn get_screen_density(&self) -> Option<u16> {
+        self.screen_density
+    }
+    pub const fn get_connections(&self) -> &Option<Vec<Connection>> {
+        &self.connections
+    }
+    pub const fn get_https_required(&self) -> Option<bool> {
+        self.https_required
+    }
+    pub const fn get_synced(&self) -> Option<bool> {
+        self.synced
+    }
+    pub const fn get_relay(&self) -> Option<bool> {
+        self.relay
+    }
+    pub const fn get_public_address_matches(&self) -> Option<bool> {
+        self.public_address_matches
+    }
+    pub const fn get_presence(&self) -> Option<bool> {
+        self.presence
+    }
+    pub const fn get_owned(&self) -> Option<bool> {
+        self.owned
+    }
+    pub const fn get_sync_list(&self) -> &Option<SyncList> {
+        &self.sync_list
+    }
+    pub const fn get_dns_rebinding_protection(&self) -> Option<bool> {
+        self.dns_rebinding_protection
+    }
+    pub const fn get_nat_loopback_supported(&self) -> Option<bool> {
+        self.nat_loopback_supported
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
 impl HasMyPlexToken for Device {
@@ -158,8 +333,18 @@ impl HasMyPlexToken for Device {
 
 impl HasDeleteUrl for Device {
     fn get_delete_url(&self) -> Option<String> {
+        let my_plex_url = self
+            .client
+            .as_ref()
+            .map_or(config::MYPLEX_URL, |c| c.get_my_plex_url());
         self.id
-            .map(|id| format!("https://plex.tv/devices/{}.xml", id))
+            .map(|id| format!("{}devices/{}.xml", my_plex_url, id))
+    }
+}
+
+impl HasPlexClient for Device {
+    fn get_client(&self) -> Option<&PlexClient> {
+        self.client.as_ref()
     }
 }
 
@@ -0,0 +1,146 @@
+use crate::MediaContainer;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct HomeUsersMediaContainer {
+    machine_identifier: Option<String>,
+    #[serde(rename = "User", default)]
+    users: Vec<HomeUser>,
+    #[serde(flatten)]
+    media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl HomeUsersMediaContainer {
+    pub const fn get_machine_identifier(&self) -> &Option<String> {
+        &self.machine_identifier
+    }
+    pub const fn get_media_container(&self) -> &MediaContainer {
+        &self.media_container
+    }
+    pub const fn get_users(&self) -> &Vec<HomeUser> {
+        &self.users
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+/// Content restrictions of a managed Home user. Profiles, which are unknown to the library,
+/// are kept as `Unknown` with the original value.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(from = "String")]
+pub enum RestrictionProfile {
+    LittleKid,
+    OlderKid,
+    Teen,
+    Unknown(String),
+}
+
+impl From<String> for RestrictionProfile {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "little_kid" => RestrictionProfile::LittleKid,
+            "older_kid" => RestrictionProfile::OlderKid,
+            "teen" => RestrictionProfile::Teen,
+            _ => RestrictionProfile::Unknown(value),
+        }
+    }
+}
+
+impl RestrictionProfile {
+    /// Returns the value, used by Plex for the profile.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RestrictionProfile::LittleKid => "little_kid",
+            RestrictionProfile::OlderKid => "older_kid",
+            RestrictionProfile::Teen => "teen",
+            RestrictionProfile::Unknown(value) => value,
+        }
+    }
+}
+
+impl serde::Serialize for RestrictionProfile {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A member of Plex Home: the admin, a user with own plex.tv account, or a managed user.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct HomeUser {
+    id: u32,
+    uuid: String,
+    title: String,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    email: String,
+    thumb: String,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    admin: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    guest: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    restricted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    restriction_profile: Option<RestrictionProfile>,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    has_password: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    protected: bool,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl HomeUser {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub fn get_uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    /// Returns plex.tv username, it's empty for managed users.
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn get_email(&self) -> &str {
+        &self.email
+    }
+    pub fn get_thumb(&self) -> &str {
+        &self.thumb
+    }
+    pub const fn get_admin(&self) -> bool {
+        self.admin
+    }
+    pub const fn get_guest(&self) -> bool {
+        self.guest
+    }
+    pub const fn get_restricted(&self) -> bool {
+        self.restricted
+    }
+    pub const fn get_restriction_profile(&self) -> &Option<RestrictionProfile> {
+        &self.restriction_profile
+    }
+    pub const fn get_has_password(&self) -> bool {
+        self.has_password
+    }
+    /// Returns `true` if the PIN is required to switch to the user.
+    pub const fn get_protected(&self) -> bool {
+        self.protected
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{MediaContainer, MediaMetadata, MediaType};
 use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
 use uuid::Uuid;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct LibraryMediaContainer {
@@ -21,30 +23,76 @@ pub struct LibraryMediaContainer {
     metadata: Option<Vec<MediaMetadata>>,
     #[serde(flatten)]
     media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-pub struct LibraryMediaContainerOuter {
-    #[serde(rename = "MediaContainer")]
-    media_container: LibraryMediaContainer,
-}
+impl LibraryMediaContainer {
+    pub const fn get_metadata(&self) -> &Option<Vec<MediaMetadata>> {
+        &self.metadata
+    }
+    pub const fn get_media_container(&self) -> &MediaContainer {
+        &self.media_container
+    }
+    pub(crate) fn into_metadata(self) -> Vec<MediaMetadata> {
+        self.metadata.unwrap_or_default()
+    }
 
-impl From<LibraryMediaContainerOuter> for LibraryMediaContainer {
-    fn from(mc: LibraryMediaContainerOuter) -> Self {
-        mc.media_container
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub const fn get_art(&self) -> &Option<String> {
+        &self.art
+    }
+    pub const fn get_content(&self) -> &Option<String> {
+        &self.content
+    }
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    pub fn get_media_tag_prefix(&self) -> &str {
+        &self.media_tag_prefix
+    }
+    pub const fn get_media_tag_version(&self) -> u64 {
+        self.media_tag_version
+    }
+    pub const fn get_title1(&self) -> &Option<String> {
+        &self.title1
+    }
+    pub const fn get_title2(&self) -> &Option<String> {
+        &self.title2
+    }
+    pub const fn get_directory(&self) -> &Option<Vec<DirectoryMediaContainer>> {
+        &self.directory
+    }
+    pub const fn get_mixed_parents(&self) -> Option<bool> {
+        self.mixed_parents
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
-struct DirectoryLocation {
+pub struct DirectoryLocation {
     id: u32,
     path: String,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl DirectoryLocation {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct DirectoryMediaContainer {
@@ -68,7 +116,9 @@ pub struct DirectoryMediaContainer {
     created_at: DateTime<Utc>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_seconds_to_datetime"
+        deserialize_with = "crate::serde_helpers::option_seconds_to_datetime",
+        serialize_with = "chrono::serde::ts_seconds_option::serialize",
+        skip_serializing_if = "Option::is_none"
     )]
     scanned_at: Option<DateTime<Utc>>,
     content: Option<bool>,
@@ -76,11 +126,86 @@ pub struct DirectoryMediaContainer {
     content_changed_at: Option<u64>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_bool_from_anything"
+        deserialize_with = "crate::serde_helpers::option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
     )]
     hidden: Option<bool>,
     #[serde(default, rename = "Location")]
     location: Vec<DirectoryLocation>,
     #[serde(skip_serializing_if = "Option::is_none")]
     enable_auto_photo_tags: Option<bool>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl DirectoryMediaContainer {
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub fn get_art(&self) -> &str {
+        &self.art
+    }
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub fn get_composite(&self) -> &str {
+        &self.composite
+    }
+    pub const fn get_filters(&self) -> bool {
+        self.filters
+    }
+    pub const fn get_refreshing(&self) -> bool {
+        self.refreshing
+    }
+    pub fn get_thumb(&self) -> &str {
+        &self.thumb
+    }
+    pub const fn get_media_type(&self) -> &MediaType {
+        &self.media_type
+    }
+    pub fn get_agent(&self) -> &str {
+        &self.agent
+    }
+    pub fn get_scanner(&self) -> &str {
+        &self.scanner
+    }
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+    pub const fn get_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+    pub const fn get_updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+    pub const fn get_created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+    pub const fn get_scanned_at(&self) -> Option<DateTime<Utc>> {
+        self.scanned_at
+    }
+    pub const fn get_content(&self) -> Option<bool> {
+        self.content
+    }
+    pub const fn get_directory(&self) -> Option<bool> {
+        self.directory
+    }
+    pub const fn get_content_changed_at(&self) -> Option<u64> {
+        self.content_changed_at
+    }
+    pub const fn get_hidden(&self) -> Option<bool> {
+        self.hidden
+    }
+    pub const fn get_location(&self) -> &Vec<DirectoryLocation> {
+        &self.location
+    }
+    pub const fn get_enable_auto_photo_tags(&self) -> Option<bool> {
+        self.enable_auto_photo_tags
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
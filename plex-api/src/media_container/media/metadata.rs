@@ -1,13 +1,18 @@
 use crate::{MediaStream, MediaType};
+use serde_json::Value;
+use std::collections::HashMap;
 use uuid::Uuid;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct MediaPart {
     id: u32,
     key: String,
-    #[serde(deserialize_with = "crate::serde_helpers::duration_from_seconds")]
+    #[serde(
+        deserialize_with = "crate::serde_helpers::duration_from_seconds",
+        serialize_with = "crate::serde_helpers::serialize_duration"
+    )]
     duration: chrono::Duration,
     file: String,
     size: u64,
@@ -17,23 +22,81 @@ pub struct MediaPart {
     video_profile: String,
     #[serde(default, rename = "Stream")]
     streams: Vec<MediaStream>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl MediaPart {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+    pub const fn get_duration(&self) -> chrono::Duration {
+        self.duration
+    }
+    pub fn get_file(&self) -> &str {
+        &self.file
+    }
+    pub const fn get_size(&self) -> u64 {
+        self.size
+    }
+    pub fn get_container(&self) -> &str {
+        &self.container
+    }
+    pub const fn get_indexes(&self) -> &Option<String> {
+        &self.indexes
+    }
+    pub fn get_audio_profile(&self) -> &str {
+        &self.audio_profile
+    }
+    pub fn get_video_profile(&self) -> &str {
+        &self.video_profile
+    }
+    pub const fn get_streams(&self) -> &Vec<MediaStream> {
+        &self.streams
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct MediaTag {
     id: Option<u32>,
     tag: String,
     filter: Option<String>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl MediaTag {
+    pub const fn get_id(&self) -> Option<u32> {
+        self.id
+    }
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+    pub const fn get_filter(&self) -> &Option<String> {
+        &self.filter
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Media {
     id: u32,
-    #[serde(deserialize_with = "crate::serde_helpers::duration_from_seconds")]
+    #[serde(
+        deserialize_with = "crate::serde_helpers::duration_from_seconds",
+        serialize_with = "crate::serde_helpers::serialize_duration"
+    )]
     duration: chrono::Duration,
     bitrate: u32,
     width: u16,
@@ -49,9 +112,62 @@ pub struct Media {
     video_profile: String,
     #[serde(rename = "Part")]
     parts: Option<Vec<MediaPart>>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl Media {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub const fn get_duration(&self) -> chrono::Duration {
+        self.duration
+    }
+    pub const fn get_bitrate(&self) -> u32 {
+        self.bitrate
+    }
+    pub const fn get_width(&self) -> u16 {
+        self.width
+    }
+    pub const fn get_height(&self) -> u16 {
+        self.height
+    }
+    pub const fn get_aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+    pub const fn get_audio_channels(&self) -> u8 {
+        self.audio_channels
+    }
+    pub fn get_audio_codec(&self) -> &str {
+        &self.audio_codec
+    }
+    pub fn get_video_codec(&self) -> &str {
+        &self.video_codec
+    }
+    pub fn get_video_resolution(&self) -> &str {
+        &self.video_resolution
+    }
+    pub fn get_container(&self) -> &str {
+        &self.container
+    }
+    pub fn get_video_frame_rate(&self) -> &str {
+        &self.video_frame_rate
+    }
+    pub fn get_audio_profile(&self) -> &str {
+        &self.audio_profile
+    }
+    pub fn get_video_profile(&self) -> &str {
+        &self.video_profile
+    }
+    pub const fn get_parts(&self) -> &Option<Vec<MediaPart>> {
+        &self.parts
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct MediaMetadata {
@@ -83,7 +199,8 @@ pub struct MediaMetadata {
     parent_index: Option<u32>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_int_from_string"
+        deserialize_with = "crate::serde_helpers::option_int_from_string",
+        skip_serializing_if = "Option::is_none"
     )]
     year: Option<u32>,
     thumb: String,
@@ -94,12 +211,16 @@ pub struct MediaMetadata {
     grandparent_theme: Option<String>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_duration_from_seconds"
+        deserialize_with = "crate::serde_helpers::option_duration_from_seconds",
+        serialize_with = "crate::serde_helpers::serialize_option_duration",
+        skip_serializing_if = "Option::is_none"
     )]
     duration: Option<chrono::Duration>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_date_from_iso"
+        deserialize_with = "crate::serde_helpers::option_date_from_iso",
+        serialize_with = "crate::serde_helpers::serialize_option_date",
+        skip_serializing_if = "Option::is_none"
     )]
     originally_available_at: Option<chrono::Date<chrono::Utc>>,
     #[serde(with = "chrono::serde::ts_seconds")]
@@ -127,11 +248,178 @@ pub struct MediaMetadata {
     tagline: Option<String>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_bool_from_anything"
+        deserialize_with = "crate::serde_helpers::option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
     )]
     has_premium_primary_extra: Option<bool>,
     primary_extra_key: Option<String>,
     rating_image: Option<String>,
     parent_summary: Option<String>,
     parent_theme: Option<String>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl MediaMetadata {
+    pub fn get_rating_key(&self) -> &str {
+        &self.rating_key
+    }
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub const fn get_media_type(&self) -> &MediaType {
+        &self.media_type
+    }
+
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub const fn get_library_section_id(&self) -> u32 {
+        self.library_section_id
+    }
+    pub fn get_library_section_title(&self) -> &str {
+        &self.library_section_title
+    }
+    pub const fn get_library_section_uuid(&self) -> &Uuid {
+        &self.library_section_uuid
+    }
+    pub const fn get_skip_parent(&self) -> Option<bool> {
+        self.skip_parent
+    }
+    pub const fn get_parent_rating_key(&self) -> &Option<String> {
+        &self.parent_rating_key
+    }
+    pub const fn get_grandparent_rating_key(&self) -> &Option<String> {
+        &self.grandparent_rating_key
+    }
+    pub const fn get_guid(&self) -> &Option<String> {
+        &self.guid
+    }
+    pub const fn get_parent_guid(&self) -> &Option<String> {
+        &self.parent_guid
+    }
+    pub const fn get_grandparent_guid(&self) -> &Option<String> {
+        &self.grandparent_guid
+    }
+    pub const fn get_grandparent_key(&self) -> &Option<String> {
+        &self.grandparent_key
+    }
+    pub const fn get_parent_key(&self) -> &Option<String> {
+        &self.parent_key
+    }
+    pub const fn get_library_section_key(&self) -> &Option<String> {
+        &self.library_section_key
+    }
+    pub const fn get_grandparent_title(&self) -> &Option<String> {
+        &self.grandparent_title
+    }
+    pub const fn get_parent_title(&self) -> &Option<String> {
+        &self.parent_title
+    }
+    pub const fn get_content_rating(&self) -> &Option<String> {
+        &self.content_rating
+    }
+    pub fn get_summary(&self) -> &str {
+        &self.summary
+    }
+    pub const fn get_index(&self) -> Option<u32> {
+        self.index
+    }
+    pub const fn get_parent_index(&self) -> Option<u32> {
+        self.parent_index
+    }
+    pub const fn get_year(&self) -> Option<u32> {
+        self.year
+    }
+    pub fn get_thumb(&self) -> &str {
+        &self.thumb
+    }
+    pub const fn get_art(&self) -> &Option<String> {
+        &self.art
+    }
+    pub const fn get_parent_thumb(&self) -> &Option<String> {
+        &self.parent_thumb
+    }
+    pub const fn get_grandparent_thumb(&self) -> &Option<String> {
+        &self.grandparent_thumb
+    }
+    pub const fn get_grandparent_art(&self) -> &Option<String> {
+        &self.grandparent_art
+    }
+    pub const fn get_grandparent_theme(&self) -> &Option<String> {
+        &self.grandparent_theme
+    }
+    pub const fn get_duration(&self) -> Option<chrono::Duration> {
+        self.duration
+    }
+    #[allow(deprecated)]
+    pub const fn get_originally_available_at(&self) -> Option<chrono::Date<chrono::Utc>> {
+        self.originally_available_at
+    }
+    pub const fn get_added_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.added_at
+    }
+    pub const fn get_updated_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.updated_at
+    }
+    pub const fn get_media(&self) -> &Vec<Media> {
+        &self.media
+    }
+    pub const fn get_genre(&self) -> &Option<Vec<MediaTag>> {
+        &self.genre
+    }
+    pub const fn get_director(&self) -> &Option<Vec<MediaTag>> {
+        &self.director
+    }
+    pub const fn get_writer(&self) -> &Option<Vec<MediaTag>> {
+        &self.writer
+    }
+    pub const fn get_country(&self) -> &Option<Vec<MediaTag>> {
+        &self.country
+    }
+    pub const fn get_role(&self) -> &Option<Vec<MediaTag>> {
+        &self.role
+    }
+    pub const fn get_leaf_count(&self) -> Option<u32> {
+        self.leaf_count
+    }
+    pub const fn get_viewed_leaf_count(&self) -> Option<u32> {
+        self.viewed_leaf_count
+    }
+    pub const fn get_loudness_analysis_version(&self) -> &Option<String> {
+        &self.loudness_analysis_version
+    }
+    pub const fn get_deep_analysis_version(&self) -> &Option<String> {
+        &self.deep_analysis_version
+    }
+    pub const fn get_studio(&self) -> &Option<String> {
+        &self.studio
+    }
+    pub const fn get_rating(&self) -> Option<f32> {
+        self.rating
+    }
+    pub const fn get_tagline(&self) -> &Option<String> {
+        &self.tagline
+    }
+    pub const fn get_has_premium_primary_extra(&self) -> Option<bool> {
+        self.has_premium_primary_extra
+    }
+    pub const fn get_primary_extra_key(&self) -> &Option<String> {
+        &self.primary_extra_key
+    }
+    pub const fn get_rating_image(&self) -> &Option<String> {
+        &self.rating_image
+    }
+    pub const fn get_parent_summary(&self) -> &Option<String> {
+        &self.parent_summary
+    }
+    pub const fn get_parent_theme(&self) -> &Option<String> {
+        &self.parent_theme
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
@@ -1,19 +1,61 @@
-use serde::{Deserialize, Deserializer};
-use serde_repr::Deserialize_repr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize_repr, Clone)]
-#[repr(u8)]
-enum MediaStreamType {
-    Video = 1,
-    Audio = 2,
-    Subtitles = 3,
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaStreamType {
+    Video,
+    Audio,
+    Subtitles,
+    Lyrics,
+    Unknown(u8),
 }
 
+impl From<u8> for MediaStreamType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => MediaStreamType::Video,
+            2 => MediaStreamType::Audio,
+            3 => MediaStreamType::Subtitles,
+            4 => MediaStreamType::Lyrics,
+            _ => MediaStreamType::Unknown(value),
+        }
+    }
+}
+
+impl From<&MediaStreamType> for u8 {
+    fn from(value: &MediaStreamType) -> Self {
+        match value {
+            MediaStreamType::Video => 1,
+            MediaStreamType::Audio => 2,
+            MediaStreamType::Subtitles => 3,
+            MediaStreamType::Lyrics => 4,
+            MediaStreamType::Unknown(value) => *value,
+        }
+    }
+}
+
+impl Serialize for MediaStreamType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(u8::from(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaStreamType {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        serde_aux::prelude::deserialize_number_from_string::<u8, D>(d).map(MediaStreamType::from)
+    }
+}
+
+/// A stream of the media part. Streams of types, which are unknown to the library, are kept as
+/// `Unknown` with the common attributes.
 #[derive(Debug, Clone)]
 pub enum MediaStream {
     Video(VideoStream),
     Audio(AudioStream),
     Subtitles(SubtitlesStream),
+    Lyrics(LyricsStream),
+    Unknown(UnknownStream),
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -55,7 +97,8 @@ struct MediaStreamStruct {
     language_code: Option<String>,
     #[serde(
         default,
-        deserialize_with = "crate::serde_helpers::option_bool_from_anything"
+        deserialize_with = "crate::serde_helpers::option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
     )]
     embedded_in_video: Option<bool>,
     extended_display_title: Option<String>,
@@ -66,13 +109,16 @@ struct MediaStreamStruct {
     loudness: Option<f32>,
     lra: Option<f32>,
     peak: Option<f32>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
 macro_rules! media_stream_enum {
     (pub struct $name:ident {
         $($field_name:ident: $field_type:ty,)+
     }) => {
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, Serialize)]
+        #[serde(rename_all = "camelCase")]
         pub struct $name {
             id: u32,
             stream_type: MediaStreamType,
@@ -80,6 +126,8 @@ macro_rules! media_stream_enum {
             index: u8,
             display_title: String,
             $($field_name: $field_type,)+
+            #[serde(flatten)]
+            extra: HashMap<String, Value>,
         }
 
         impl From<MediaStreamStruct> for $name {
@@ -90,10 +138,32 @@ macro_rules! media_stream_enum {
                     codec: stream.codec,
                     index: stream.index,
                     display_title: stream.display_title,
-                    $($field_name: <$field_type>::convert(stringify!($field_name), stream.$field_name)),+
+                    $($field_name: <$field_type>::convert(stringify!($field_name), stream.$field_name),)+
+                    extra: stream.extra,
                 }
             }
         }
+
+        impl $name {
+            pub const fn get_id(&self) -> u32 {
+                self.id
+            }
+            pub const fn get_stream_type(&self) -> &MediaStreamType {
+                &self.stream_type
+            }
+            pub fn get_codec(&self) -> &str {
+                &self.codec
+            }
+            pub const fn get_index(&self) -> u8 {
+                self.index
+            }
+            pub fn get_display_title(&self) -> &str {
+                &self.display_title
+            }
+            pub const fn get_extra(&self) -> &HashMap<String, Value> {
+                &self.extra
+            }
+        }
     }
 }
 
@@ -143,6 +213,69 @@ media_stream_enum! {
     }
 }
 
+impl VideoStream {
+    pub const fn get_default(&self) -> bool {
+        self.default
+    }
+    pub const fn get_bitrate(&self) -> u32 {
+        self.bitrate
+    }
+    pub fn get_chroma_subsampling(&self) -> &str {
+        &self.chroma_subsampling
+    }
+    pub fn get_chroma_location(&self) -> &str {
+        &self.chroma_location
+    }
+    pub fn get_closed_captions(&self) -> &str {
+        &self.closed_captions
+    }
+    pub fn get_coded_height(&self) -> &str {
+        &self.coded_height
+    }
+    pub fn get_coded_width(&self) -> &str {
+        &self.coded_width
+    }
+    pub fn get_color_primaries(&self) -> &str {
+        &self.color_primaries
+    }
+    pub fn get_color_space(&self) -> &str {
+        &self.color_space
+    }
+    pub fn get_color_range(&self) -> &str {
+        &self.color_range
+    }
+    pub fn get_color_trc(&self) -> &str {
+        &self.color_trc
+    }
+    pub const fn get_frame_rate(&self) -> f32 {
+        self.frame_rate
+    }
+    pub const fn get_height(&self) -> u16 {
+        self.height
+    }
+    pub const fn get_width(&self) -> u16 {
+        self.width
+    }
+    pub const fn get_level(&self) -> u16 {
+        self.level
+    }
+    pub fn get_profile(&self) -> &str {
+        &self.profile
+    }
+    pub const fn get_ref_frames(&self) -> u64 {
+        self.ref_frames
+    }
+    pub const fn get_has_scaling_matrix(&self) -> bool {
+        self.has_scaling_matrix
+    }
+    pub fn get_scan_type(&self) -> &str {
+        &self.scan_type
+    }
+    pub const fn get_bit_depth(&self) -> u16 {
+        self.bit_depth
+    }
+}
+
 media_stream_enum! {
     pub struct AudioStream {
         default: Option<bool>,
@@ -164,6 +297,57 @@ media_stream_enum! {
     }
 }
 
+impl AudioStream {
+    pub const fn get_default(&self) -> Option<bool> {
+        self.default
+    }
+    pub const fn get_selected(&self) -> bool {
+        self.selected
+    }
+    pub const fn get_bitrate(&self) -> u32 {
+        self.bitrate
+    }
+    pub fn get_profile(&self) -> &str {
+        &self.profile
+    }
+    pub const fn get_sampling_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+    pub const fn get_channels(&self) -> u8 {
+        self.channels
+    }
+    pub fn get_audio_channel_layout(&self) -> &str {
+        &self.audio_channel_layout
+    }
+    pub const fn get_album_gain(&self) -> Option<f32> {
+        self.album_gain
+    }
+    pub const fn get_album_peak(&self) -> Option<f32> {
+        self.album_peak
+    }
+    pub const fn get_album_range(&self) -> Option<f32> {
+        self.album_range
+    }
+    pub const fn get_bit_depth(&self) -> Option<u16> {
+        self.bit_depth
+    }
+    pub const fn get_extended_display_title(&self) -> &Option<String> {
+        &self.extended_display_title
+    }
+    pub const fn get_gain(&self) -> Option<f32> {
+        self.gain
+    }
+    pub const fn get_loudness(&self) -> Option<f32> {
+        self.loudness
+    }
+    pub const fn get_lra(&self) -> Option<f32> {
+        self.lra
+    }
+    pub const fn get_peak(&self) -> Option<f32> {
+        self.peak
+    }
+}
+
 media_stream_enum! {
     pub struct SubtitlesStream {
         selected: bool,
@@ -172,12 +356,75 @@ media_stream_enum! {
     }
 }
 
+impl SubtitlesStream {
+    pub const fn get_selected(&self) -> bool {
+        self.selected
+    }
+    pub const fn get_bitrate(&self) -> Option<u32> {
+        self.bitrate
+    }
+    pub const fn get_embedded_in_video(&self) -> bool {
+        self.embedded_in_video
+    }
+}
+
+media_stream_enum! {
+    pub struct LyricsStream {
+        key: Option<String>,
+        language: Option<String>,
+        language_code: Option<String>,
+    }
+}
+
+media_stream_enum! {
+    pub struct UnknownStream {
+        key: Option<String>,
+        title: Option<String>,
+    }
+}
+
+impl LyricsStream {
+    pub const fn get_key(&self) -> &Option<String> {
+        &self.key
+    }
+    pub const fn get_language(&self) -> &Option<String> {
+        &self.language
+    }
+    pub const fn get_language_code(&self) -> &Option<String> {
+        &self.language_code
+    }
+}
+
+impl UnknownStream {
+    pub const fn get_key(&self) -> &Option<String> {
+        &self.key
+    }
+    pub const fn get_title(&self) -> &Option<String> {
+        &self.title
+    }
+}
+
 impl MediaStream {
     fn new(stream: MediaStreamStruct) -> Self {
         match stream.stream_type {
             MediaStreamType::Video => MediaStream::Video(VideoStream::from(stream)),
             MediaStreamType::Audio => MediaStream::Audio(AudioStream::from(stream)),
             MediaStreamType::Subtitles => MediaStream::Subtitles(SubtitlesStream::from(stream)),
+            MediaStreamType::Lyrics => MediaStream::Lyrics(LyricsStream::from(stream)),
+            MediaStreamType::Unknown(_) => MediaStream::Unknown(UnknownStream::from(stream)),
+        }
+    }
+}
+
+/// Serialized the same way as received, so it's deserialized back into the same variant.
+impl Serialize for MediaStream {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MediaStream::Video(stream) => stream.serialize(serializer),
+            MediaStream::Audio(stream) => stream.serialize(serializer),
+            MediaStream::Subtitles(stream) => stream.serialize(serializer),
+            MediaStream::Lyrics(stream) => stream.serialize(serializer),
+            MediaStream::Unknown(stream) => stream.serialize(serializer),
         }
     }
 }
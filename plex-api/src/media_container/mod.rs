@@ -1,43 +1,111 @@
 mod device;
+mod home;
 mod library;
 mod media;
 mod server;
 mod settings;
+mod sharing;
 mod user;
 
 pub use self::device::*;
+pub use self::home::*;
 pub use self::library::*;
 pub use self::media::*;
 pub use self::server::*;
 pub use self::settings::*;
+pub use self::sharing::*;
 pub use self::user::*;
 
 use crate::serde_helpers::option_int_from_string;
+use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize, Clone)]
+/// Attributes, common for all the containers. It's flattened into them, so unknown attributes
+/// are collected by the outer container.
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct MediaContainer {
-    #[serde(deserialize_with = "option_int_from_string", default)]
+    #[serde(
+        deserialize_with = "option_int_from_string",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     size: Option<u32>,
-    #[serde(deserialize_with = "option_int_from_string", default)]
+    #[serde(
+        deserialize_with = "option_int_from_string",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     total_size: Option<u32>,
+    #[serde(
+        deserialize_with = "option_int_from_string",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    offset: Option<u32>,
+    // Some of the containers have the same attributes, which shouldn't be overwritten with `null`
+    #[serde(skip_serializing_if = "Option::is_none")]
     public_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     friendly_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     identifier: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl MediaContainer {
+    /// Returns number of items in the container.
+    pub const fn get_size(&self) -> Option<u32> {
+        self.size
+    }
+    /// Returns index of the first item, when the container is a page of a bigger one.
+    pub const fn get_offset(&self) -> Option<u32> {
+        self.offset
+    }
+    /// Returns total number of items, when the container is a page of a bigger one.
+    pub const fn get_total_size(&self) -> Option<u32> {
+        self.total_size
+    }
+    pub const fn get_public_address(&self) -> &Option<String> {
+        &self.public_address
+    }
+    pub const fn get_friendly_name(&self) -> &Option<String> {
+        &self.friendly_name
+    }
+    pub const fn get_identifier(&self) -> &Option<String> {
+        &self.identifier
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 pub struct Directory {
     count: u16,
     key: String,
     title: String,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-#[serde(rename_all = "lowercase")]
+impl Directory {
+    pub const fn get_count(&self) -> u16 {
+        self.count
+    }
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+/// Type of the library item. Types, which are unknown to the library, are kept as
+/// `Unknown` with the original value.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(from = "String")]
 pub enum MediaType {
     Movie,
     Show,
@@ -47,4 +115,53 @@ pub enum MediaType {
     Episode,
     Season,
     Track,
+    Clip,
+    Collection,
+    Playlist,
+    Unknown(String),
+}
+
+impl From<String> for MediaType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "movie" => MediaType::Movie,
+            "show" => MediaType::Show,
+            "artist" => MediaType::Artist,
+            "album" => MediaType::Album,
+            "photo" => MediaType::Photo,
+            "episode" => MediaType::Episode,
+            "season" => MediaType::Season,
+            "track" => MediaType::Track,
+            "clip" => MediaType::Clip,
+            "collection" => MediaType::Collection,
+            "playlist" => MediaType::Playlist,
+            _ => MediaType::Unknown(value),
+        }
+    }
+}
+
+impl MediaType {
+    /// Returns the value, used by Plex for the type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            MediaType::Movie => "movie",
+            MediaType::Show => "show",
+            MediaType::Artist => "artist",
+            MediaType::Album => "album",
+            MediaType::Photo => "photo",
+            MediaType::Episode => "episode",
+            MediaType::Season => "season",
+            MediaType::Track => "track",
+            MediaType::Clip => "clip",
+            MediaType::Collection => "collection",
+            MediaType::Playlist => "playlist",
+            MediaType::Unknown(value) => value,
+        }
+    }
+}
+
+impl serde::Serialize for MediaType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
 }
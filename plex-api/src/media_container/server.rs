@@ -6,8 +6,10 @@ use crate::serde_helpers::{
 use chrono::{DateTime, Utc};
 use semver::Version;
 use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct ServerMediaContainer {
@@ -44,20 +46,46 @@ pub struct ServerMediaContainer {
     #[serde(default)]
     country_code: String,
     livetv: u8,
-    #[serde(default, deserialize_with = "option_comma_separated_to_vec")]
+    #[serde(
+        default,
+        deserialize_with = "option_comma_separated_to_vec",
+        serialize_with = "crate::serde_helpers::serialize_option_comma_separated",
+        skip_serializing_if = "Option::is_none"
+    )]
     diagnostics: Option<Vec<String>>,
-    #[serde(default, deserialize_with = "option_bool_from_anything")]
+    #[serde(
+        default,
+        deserialize_with = "option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
+    )]
     media_providers: Option<bool>,
-    #[serde(default, deserialize_with = "option_bool_from_anything")]
+    #[serde(
+        default,
+        deserialize_with = "option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
+    )]
     multiuser: Option<bool>,
-    #[serde(default, deserialize_with = "option_bool_from_anything")]
+    #[serde(
+        default,
+        deserialize_with = "option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
+    )]
     my_plex: Option<bool>,
-    #[serde(default, deserialize_with = "option_bool_from_anything")]
+    #[serde(
+        default,
+        deserialize_with = "option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
+    )]
     my_plex_subscription: Option<bool>,
     my_plex_mapping_state: Option<String>,
     my_plex_signin_state: Option<String>,
     my_plex_username: Option<String>,
-    #[serde(deserialize_with = "option_comma_separated_to_vec", default)]
+    #[serde(
+        deserialize_with = "option_comma_separated_to_vec",
+        default,
+        serialize_with = "crate::serde_helpers::serialize_option_comma_separated",
+        skip_serializing_if = "Option::is_none"
+    )]
     owner_features: Option<Vec<String>>,
 
     #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
@@ -96,25 +124,54 @@ pub struct ServerMediaContainer {
     updater: bool,
     #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
     voice_search: bool,
-    #[serde(deserialize_with = "option_comma_separated_to_vec", default)]
+    #[serde(
+        deserialize_with = "option_comma_separated_to_vec",
+        default,
+        serialize_with = "crate::serde_helpers::serialize_option_comma_separated",
+        skip_serializing_if = "Option::is_none"
+    )]
     transcoder_video_bitrates: Option<Vec<u16>>,
-    #[serde(deserialize_with = "option_comma_separated_to_vec", default)]
+    #[serde(
+        deserialize_with = "option_comma_separated_to_vec",
+        default,
+        serialize_with = "crate::serde_helpers::serialize_option_comma_separated",
+        skip_serializing_if = "Option::is_none"
+    )]
     transcoder_video_qualities: Option<Vec<u8>>,
-    #[serde(deserialize_with = "option_comma_separated_to_vec", default)]
+    #[serde(
+        deserialize_with = "option_comma_separated_to_vec",
+        default,
+        serialize_with = "crate::serde_helpers::serialize_option_comma_separated",
+        skip_serializing_if = "Option::is_none"
+    )]
     transcoder_video_resolutions: Option<Vec<u16>>,
-    #[serde(default, deserialize_with = "option_seconds_to_datetime")]
+    #[serde(
+        default,
+        deserialize_with = "option_seconds_to_datetime",
+        serialize_with = "chrono::serde::ts_seconds_option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     updated_at: Option<DateTime<Utc>>,
-    #[serde(deserialize_with = "plex_version_deserialize")]
+    #[serde(
+        deserialize_with = "plex_version_deserialize",
+        serialize_with = "plex_version_serialize"
+    )]
     version: Version,
 
     max_upload_bitrate: Option<u16>,
     max_upload_bitrate_reason: Option<String>,
     max_upload_bitrate_reason_message: Option<String>,
-    #[serde(default, deserialize_with = "option_bool_from_anything")]
+    #[serde(
+        default,
+        deserialize_with = "option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
+    )]
     push_notifications: Option<bool>,
 
     #[serde(flatten)]
     media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
 impl ServerMediaContainer {
@@ -124,6 +181,160 @@ impl ServerMediaContainer {
     pub const fn get_version(&self) -> &Version {
         &self.version
     }
+
+    pub fn get_machine_identifier(&self) -> &str {
+        &self.machine_identifier
+    }
+    pub const fn get_directories(&self) -> &Vec<Directory> {
+        &self.directories
+    }
+    pub const fn get_allow_camera_upload(&self) -> bool {
+        self.allow_camera_upload
+    }
+    pub const fn get_allow_channel_access(&self) -> bool {
+        self.allow_channel_access
+    }
+    pub const fn get_allow_media_deletion(&self) -> bool {
+        self.allow_media_deletion
+    }
+    pub const fn get_allow_sharing(&self) -> bool {
+        self.allow_sharing
+    }
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub const fn get_allow_tuners(&self) -> bool {
+        self.allow_tuners
+    }
+    pub const fn get_background_processing(&self) -> bool {
+        self.background_processing
+    }
+    pub const fn get_certificate(&self) -> bool {
+        self.certificate
+    }
+    pub const fn get_companion_proxy(&self) -> bool {
+        self.companion_proxy
+    }
+    pub fn get_country_code(&self) -> &str {
+        &self.country_code
+    }
+    pub const fn get_livetv(&self) -> u8 {
+        self.livetv
+    }
+    pub const fn get_diagnostics(&self) -> &Option<Vec<String>> {
+        &self.diagnostics
+    }
+    pub const fn get_media_providers(&self) -> Option<bool> {
+        self.media_providers
+    }
+    pub const fn get_multiuser(&self) -> Option<bool> {
+        self.multiuser
+    }
+    pub const fn get_my_plex(&self) -> Option<bool> {
+        self.my_plex
+    }
+    pub const fn get_my_plex_subscription(&self) -> Option<bool> {
+        self.my_plex_subscription
+    }
+    pub const fn get_my_plex_mapping_state(&self) -> &Option<String> {
+        &self.my_plex_mapping_state
+    }
+    pub const fn get_my_plex_signin_state(&self) -> &Option<String> {
+        &self.my_plex_signin_state
+    }
+    pub const fn get_my_plex_username(&self) -> &Option<String> {
+        &self.my_plex_username
+    }
+    pub const fn get_owner_features(&self) -> &Option<Vec<String>> {
+        &self.owner_features
+    }
+    pub const fn get_event_stream(&self) -> bool {
+        self.event_stream
+    }
+    pub const fn get_hub_search(&self) -> bool {
+        self.hub_search
+    }
+    pub const fn get_item_clusters(&self) -> bool {
+        self.item_clusters
+    }
+    pub const fn get_photo_auto_tag(&self) -> bool {
+        self.photo_auto_tag
+    }
+    pub fn get_platform(&self) -> &str {
+        &self.platform
+    }
+    pub fn get_platform_version(&self) -> &str {
+        &self.platform_version
+    }
+    pub const fn get_plugin_host(&self) -> bool {
+        self.plugin_host
+    }
+    pub const fn get_request_parameters_in_cookie(&self) -> bool {
+        self.request_parameters_in_cookie
+    }
+    pub const fn get_read_only_libraries(&self) -> u16 {
+        self.read_only_libraries
+    }
+    pub const fn get_streaming_brain_abr_version(&self) -> Option<u8> {
+        self.streaming_brain_abr_version
+    }
+    pub const fn get_streaming_brain_version(&self) -> u8 {
+        self.streaming_brain_version
+    }
+    pub const fn get_sync(&self) -> bool {
+        self.sync
+    }
+    pub const fn get_transcoder_active_video_sessions(&self) -> u8 {
+        self.transcoder_active_video_sessions
+    }
+    pub const fn get_transcoder_audio(&self) -> bool {
+        self.transcoder_audio
+    }
+    pub const fn get_transcoder_lyrics(&self) -> bool {
+        self.transcoder_lyrics
+    }
+    pub const fn get_transcoder_photo(&self) -> bool {
+        self.transcoder_photo
+    }
+    pub const fn get_transcoder_subtitles(&self) -> bool {
+        self.transcoder_subtitles
+    }
+    pub const fn get_transcoder_video(&self) -> bool {
+        self.transcoder_video
+    }
+    pub const fn get_updater(&self) -> bool {
+        self.updater
+    }
+    pub const fn get_voice_search(&self) -> bool {
+        self.voice_search
+    }
+    pub const fn get_transcoder_video_bitrates(&self) -> &Option<Vec<u16>> {
+        &self.transcoder_video_bitrates
+    }
+    pub const fn get_transcoder_video_qualities(&self) -> &Option<Vec<u8>> {
+        &self.transcoder_video_qualities
+    }
+    pub const fn get_transcoder_video_resolutions(&self) -> &Option<Vec<u16>> {
+        &self.transcoder_video_resolutions
+    }
+    pub const fn get_updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+    pub const fn get_max_upload_bitrate(&self) -> Option<u16> {
+        self.max_upload_bitrate
+    }
+    pub const fn get_max_upload_bitrate_reason(&self) -> &Option<String> {
+        &self.max_upload_bitrate_reason
+    }
+    pub const fn get_max_upload_bitrate_reason_message(&self) -> &Option<String> {
+        &self.max_upload_bitrate_reason_message
+    }
+    pub const fn get_push_notifications(&self) -> Option<bool> {
+        self.push_notifications
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
 fn plex_version_deserialize<'de, D>(d: D) -> Result<Version, D::Error>
@@ -153,3 +364,11 @@ where
         Err(e) => Err(e),
     }
 }
+
+/// Restores the original format of the version, e.g. `1.14.1.5488-cc260c476`.
+fn plex_version_serialize<S>(version: &Version, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&version.to_string().replacen('+', ".", 1))
+}
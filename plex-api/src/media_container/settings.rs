@@ -1,21 +1,25 @@
 use crate::serde_helpers::option_pipe_separated_to_vec;
 use crate::{MediaContainer, PlexApiError};
+use serde_json::Value;
 use std::collections::HashMap;
 use thiserror::Error;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct SettingsMediaContainer {
     #[serde(
         rename = "Setting",
-        deserialize_with = "deserialize_settings_as_hashmap"
+        deserialize_with = "deserialize_settings_as_hashmap",
+        serialize_with = "serialize_settings_as_vec"
     )]
     settings: HashMap<String, Setting>,
     #[serde(flatten)]
     media_container: MediaContainer,
     #[serde(skip)]
     updated: HashMap<String, Setting>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
 impl SettingsMediaContainer {
@@ -63,23 +67,20 @@ impl SettingsMediaContainer {
     pub const fn get_changed(&self) -> &HashMap<String, Setting> {
         &self.updated
     }
-}
-
-#[derive(Debug, Deserialize, Clone)]
-#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-pub(crate) struct SettingsMediaContainerOuter {
-    #[serde(rename = "MediaContainer")]
-    media_container: SettingsMediaContainer,
-}
 
-impl From<SettingsMediaContainerOuter> for SettingsMediaContainer {
-    fn from(mc: SettingsMediaContainerOuter) -> Self {
-        mc.media_container
+    pub const fn get_settings(&self) -> &HashMap<String, Setting> {
+        &self.settings
+    }
+    pub const fn get_media_container(&self) -> &MediaContainer {
+        &self.media_container
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
     }
 }
 
 // TODO: enable `serde(deny_unknown_fields)`
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Setting {
     id: String,
     label: String,
@@ -103,6 +104,32 @@ impl Setting {
     pub fn get_value(&self) -> SettingValue {
         SettingValue::from(&self.payload)
     }
+
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+    pub fn get_summary(&self) -> &str {
+        &self.summary
+    }
+    pub const fn get_hidden(&self) -> bool {
+        self.hidden
+    }
+    pub const fn get_advanced(&self) -> bool {
+        self.advanced
+    }
+    pub fn get_group(&self) -> &str {
+        &self.group
+    }
+    /// Returns attributes, which are unknown to the model. They're collected by the payload, as
+    /// it's flattened into the setting.
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        match &self.payload {
+            Payload::Bool { extra, .. }
+            | Payload::Int { extra, .. }
+            | Payload::Text { extra, .. }
+            | Payload::Double { extra, .. } => extra,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -136,6 +163,16 @@ impl std::str::FromStr for SettingEnumValueString {
     }
 }
 
+impl std::fmt::Display for SettingEnumValueString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == self.1 {
+            write!(f, "{}", self.0)
+        } else {
+            write!(f, "{}:{}", self.0, self.1)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SettingEnumValueInt(i32, String);
 
@@ -159,7 +196,17 @@ impl std::str::FromStr for SettingEnumValueInt {
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl std::fmt::Display for SettingEnumValueInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.to_string() == self.1 {
+            write!(f, "{}", self.0)
+        } else {
+            write!(f, "{}:{}", self.0, self.1)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub(crate) enum Payload {
     Bool {
@@ -167,6 +214,8 @@ pub(crate) enum Payload {
         default: bool,
         #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
         value: bool,
+        #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+        extra: HashMap<String, Value>,
     },
     Int {
         #[serde(deserialize_with = "serde_aux::prelude::deserialize_number_from_string")]
@@ -176,9 +225,13 @@ pub(crate) enum Payload {
         #[serde(
             rename = "enumValues",
             deserialize_with = "option_pipe_separated_to_vec",
-            default
+            default,
+            serialize_with = "crate::serde_helpers::serialize_option_pipe_separated",
+            skip_serializing_if = "Option::is_none"
         )]
         enum_values: Option<Vec<SettingEnumValueInt>>,
+        #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+        extra: HashMap<String, Value>,
     },
     Text {
         default: String,
@@ -186,15 +239,21 @@ pub(crate) enum Payload {
         #[serde(
             rename = "enumValues",
             deserialize_with = "option_pipe_separated_to_vec",
-            default
+            default,
+            serialize_with = "crate::serde_helpers::serialize_option_pipe_separated",
+            skip_serializing_if = "Option::is_none"
         )]
         enum_values: Option<Vec<SettingEnumValueString>>,
+        #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+        extra: HashMap<String, Value>,
     },
     Double {
         #[serde(deserialize_with = "serde_aux::prelude::deserialize_number_from_string")]
         default: f64,
         #[serde(deserialize_with = "serde_aux::prelude::deserialize_number_from_string")]
         value: f64,
+        #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+        extra: HashMap<String, Value>,
     },
 }
 
@@ -327,3 +386,15 @@ where
     }
     Ok(map)
 }
+
+fn serialize_settings_as_vec<S>(
+    settings: &HashMap<String, Setting>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut settings: Vec<_> = settings.iter().collect();
+    settings.sort_unstable_by_key(|(id, _)| *id);
+    serializer.collect_seq(settings.into_iter().map(|(_, setting)| setting))
+}
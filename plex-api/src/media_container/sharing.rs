@@ -0,0 +1,309 @@
+use crate::serde_helpers::option_seconds_to_datetime;
+use crate::{ContentFilter, MediaContainer};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct SharedServersMediaContainer {
+    machine_identifier: Option<String>,
+    #[serde(rename = "SharedServer", default)]
+    shared_servers: Vec<SharedServer>,
+    #[serde(flatten)]
+    media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl SharedServersMediaContainer {
+    pub const fn get_machine_identifier(&self) -> &Option<String> {
+        &self.machine_identifier
+    }
+    pub const fn get_media_container(&self) -> &MediaContainer {
+        &self.media_container
+    }
+    pub const fn get_shared_servers(&self) -> &Vec<SharedServer> {
+        &self.shared_servers
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+/// The server, shared with a friend, with the library sections available to them.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct SharedServer {
+    id: u32,
+    username: String,
+    email: String,
+    #[serde(rename = "userID")]
+    user_id: u32,
+    #[serde(default)]
+    access_token: String,
+    name: String,
+    #[serde(
+        default,
+        deserialize_with = "option_seconds_to_datetime",
+        serialize_with = "chrono::serde::ts_seconds_option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    accepted_at: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        deserialize_with = "option_seconds_to_datetime",
+        serialize_with = "chrono::serde::ts_seconds_option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    invited_at: Option<DateTime<Utc>>,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    allow_sync: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    allow_camera_upload: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    allow_channels: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    owned: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    all_libraries: bool,
+    filter_all: ContentFilter,
+    filter_movies: ContentFilter,
+    filter_music: ContentFilter,
+    filter_photos: ContentFilter,
+    filter_television: ContentFilter,
+    #[serde(rename = "Section", default)]
+    sections: Vec<SharedSection>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl SharedServer {
+    /// Returns id of the share, which is used to update the shared sections.
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn get_email(&self) -> &str {
+        &self.email
+    }
+    pub const fn get_user_id(&self) -> u32 {
+        self.user_id
+    }
+    pub fn get_access_token(&self) -> &str {
+        &self.access_token
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the time the invite was accepted, it's `None` while the invite is pending.
+    pub const fn get_accepted_at(&self) -> Option<DateTime<Utc>> {
+        self.accepted_at
+    }
+    pub const fn get_invited_at(&self) -> Option<DateTime<Utc>> {
+        self.invited_at
+    }
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub const fn get_allow_camera_upload(&self) -> bool {
+        self.allow_camera_upload
+    }
+    pub const fn get_allow_channels(&self) -> bool {
+        self.allow_channels
+    }
+    pub const fn get_owned(&self) -> bool {
+        self.owned
+    }
+    pub const fn get_all_libraries(&self) -> bool {
+        self.all_libraries
+    }
+    pub const fn get_filter_all(&self) -> &ContentFilter {
+        &self.filter_all
+    }
+    pub const fn get_filter_movies(&self) -> &ContentFilter {
+        &self.filter_movies
+    }
+    pub const fn get_filter_music(&self) -> &ContentFilter {
+        &self.filter_music
+    }
+    pub const fn get_filter_photos(&self) -> &ContentFilter {
+        &self.filter_photos
+    }
+    pub const fn get_filter_television(&self) -> &ContentFilter {
+        &self.filter_television
+    }
+    pub const fn get_sections(&self) -> &Vec<SharedSection> {
+        &self.sections
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+/// A library section of the server, as it's known to plex.tv.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct SharedSection {
+    id: u32,
+    key: String,
+    title: String,
+    #[serde(rename = "type")]
+    section_type: String,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde_helpers::option_bool_from_anything",
+        skip_serializing_if = "Option::is_none"
+    )]
+    shared: Option<bool>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl SharedSection {
+    /// Returns plex.tv's id of the section, which is used when sharing it.
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    /// Returns the key of the section on the server.
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub fn get_section_type(&self) -> &str {
+        &self.section_type
+    }
+    /// Returns `true` if the section is shared, available for the sections of a
+    /// [`SharedServer`] only.
+    ///
+    /// [`SharedServer`]: struct.SharedServer.html
+    pub const fn get_shared(&self) -> Option<bool> {
+        self.shared
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct InvitesMediaContainer {
+    machine_identifier: Option<String>,
+    #[serde(rename = "Invite", default)]
+    invites: Vec<Invite>,
+    #[serde(flatten)]
+    media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl InvitesMediaContainer {
+    pub const fn get_machine_identifier(&self) -> &Option<String> {
+        &self.machine_identifier
+    }
+    pub const fn get_media_container(&self) -> &MediaContainer {
+        &self.media_container
+    }
+    pub const fn get_invites(&self) -> &Vec<Invite> {
+        &self.invites
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+/// An invite to become a friend, to join a Home or to access a server.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct Invite {
+    id: u32,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    friend: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    home: bool,
+    #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
+    server: bool,
+    username: String,
+    email: String,
+    thumb: String,
+    friendly_name: String,
+    #[serde(rename = "Server", default)]
+    servers: Vec<InviteServer>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl Invite {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub const fn get_created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+    pub const fn get_friend(&self) -> bool {
+        self.friend
+    }
+    pub const fn get_home(&self) -> bool {
+        self.home
+    }
+    pub const fn get_server(&self) -> bool {
+        self.server
+    }
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn get_email(&self) -> &str {
+        &self.email
+    }
+    pub fn get_thumb(&self) -> &str {
+        &self.thumb
+    }
+    pub fn get_friendly_name(&self) -> &str {
+        &self.friendly_name
+    }
+    pub const fn get_servers(&self) -> &Vec<InviteServer> {
+        &self.servers
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct InviteServer {
+    name: String,
+    num_libraries: u32,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl InviteServer {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub const fn get_num_libraries(&self) -> u32 {
+        self.num_libraries
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
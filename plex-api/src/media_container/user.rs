@@ -1,8 +1,11 @@
 use crate::MediaContainer;
 use chrono::{DateTime, Utc};
-use serde_repr::Deserialize_repr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct UsersMediaContainer {
@@ -11,17 +14,198 @@ pub struct UsersMediaContainer {
     users: Vec<User>,
     #[serde(flatten)]
     media_container: MediaContainer,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize_repr, Clone)]
-#[repr(u8)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum AllowTuners {
-    None = 0,
-    AllowLiveTv = 1,
-    AllowLiveTvAndDvr = 2,
+    #[default]
+    None,
+    AllowLiveTv,
+    AllowLiveTvAndDvr,
+    Unknown(u8),
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl From<u8> for AllowTuners {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AllowTuners::None,
+            1 => AllowTuners::AllowLiveTv,
+            2 => AllowTuners::AllowLiveTvAndDvr,
+            _ => AllowTuners::Unknown(value),
+        }
+    }
+}
+
+impl From<&AllowTuners> for u8 {
+    fn from(value: &AllowTuners) -> Self {
+        match value {
+            AllowTuners::None => 0,
+            AllowTuners::AllowLiveTv => 1,
+            AllowTuners::AllowLiveTvAndDvr => 2,
+            AllowTuners::Unknown(value) => *value,
+        }
+    }
+}
+
+impl Serialize for AllowTuners {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(u8::from(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for AllowTuners {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        serde_aux::prelude::deserialize_number_from_string::<u8, D>(d).map(AllowTuners::from)
+    }
+}
+
+/// Content restrictions of a shared or managed user for a type of media, stored by Plex as
+/// `contentRating=G%2CPG|label!=Horror`. Unknown restrictions are kept as they are.
+///
+/// # Examples
+///
+/// ```
+/// use plex_api::ContentFilter;
+///
+/// let filter = ContentFilter::default()
+///     .allow_content_rating("G")
+///     .allow_content_rating("PG")
+///     .exclude_label("Horror");
+/// assert_eq!("contentRating=G%2CPG|label!=Horror", filter.to_string());
+/// assert_eq!(filter, ContentFilter::from("contentRating=G%2CPG|label!=Horror"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(from = "String")]
+pub struct ContentFilter {
+    allowed_content_ratings: Vec<String>,
+    excluded_content_ratings: Vec<String>,
+    included_labels: Vec<String>,
+    excluded_labels: Vec<String>,
+    extra: Vec<(String, String)>,
+}
+
+impl ContentFilter {
+    /// Allows content with the rating only, e.g. `G` or `TV-14`.
+    pub fn allow_content_rating(mut self, content_rating: &str) -> Self {
+        self.allowed_content_ratings.push(content_rating.to_owned());
+        self
+    }
+
+    pub fn exclude_content_rating(mut self, content_rating: &str) -> Self {
+        self.excluded_content_ratings
+            .push(content_rating.to_owned());
+        self
+    }
+
+    /// Allows content with the label only.
+    pub fn include_label(mut self, label: &str) -> Self {
+        self.included_labels.push(label.to_owned());
+        self
+    }
+
+    pub fn exclude_label(mut self, label: &str) -> Self {
+        self.excluded_labels.push(label.to_owned());
+        self
+    }
+
+    /// Returns `true` if the filter doesn't restrict anything.
+    pub fn is_empty(&self) -> bool {
+        self.allowed_content_ratings.is_empty()
+            && self.excluded_content_ratings.is_empty()
+            && self.included_labels.is_empty()
+            && self.excluded_labels.is_empty()
+            && self.extra.is_empty()
+    }
+
+    pub fn get_allowed_content_ratings(&self) -> &[String] {
+        &self.allowed_content_ratings
+    }
+    pub fn get_excluded_content_ratings(&self) -> &[String] {
+        &self.excluded_content_ratings
+    }
+    pub fn get_included_labels(&self) -> &[String] {
+        &self.included_labels
+    }
+    pub fn get_excluded_labels(&self) -> &[String] {
+        &self.excluded_labels
+    }
+    /// Returns restrictions, unknown to the library, as pairs of the key and the raw value.
+    pub fn get_extra(&self) -> &[(String, String)] {
+        &self.extra
+    }
+}
+
+impl From<&str> for ContentFilter {
+    fn from(value: &str) -> Self {
+        let mut filter = ContentFilter::default();
+        for part in value.split('|').filter(|part| !part.is_empty()) {
+            let (key, raw) = part.split_at(part.find('=').unwrap_or(part.len()));
+            let raw = raw.strip_prefix('=').unwrap_or(raw);
+            let values = match key {
+                "contentRating" => &mut filter.allowed_content_ratings,
+                "contentRating!" => &mut filter.excluded_content_ratings,
+                "label" => &mut filter.included_labels,
+                "label!" => &mut filter.excluded_labels,
+                _ => {
+                    filter.extra.push((key.to_owned(), raw.to_owned()));
+                    continue;
+                }
+            };
+            values.extend(
+                url::form_urlencoded::parse(format!("v={}", raw).as_bytes()).flat_map(|(_, v)| {
+                    v.split(',')
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_owned)
+                        .collect::<Vec<_>>()
+                }),
+            );
+        }
+        filter
+    }
+}
+
+impl From<String> for ContentFilter {
+    fn from(value: String) -> Self {
+        ContentFilter::from(value.as_str())
+    }
+}
+
+impl fmt::Display for ContentFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let known = [
+            ("contentRating", &self.allowed_content_ratings),
+            ("contentRating!", &self.excluded_content_ratings),
+            ("label", &self.included_labels),
+            ("label!", &self.excluded_labels),
+        ];
+        let parts = known
+            .iter()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(key, values)| {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|v| url::form_urlencoded::byte_serialize(v.as_bytes()).collect())
+                    .collect();
+                format!("{}={}", key, values.join("%2C"))
+            })
+            .chain(
+                self.extra
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value)),
+            );
+        write!(f, "{}", parts.collect::<Vec<_>>().join("|"))
+    }
+}
+
+impl Serialize for ContentFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct User {
@@ -43,19 +227,78 @@ pub struct User {
     allow_subtitle_admin: bool,
     #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
     restricted: bool,
-    filter_all: String,
-    filter_movies: String,
-    filter_music: String,
-    filter_photos: String,
-    filter_television: String,
+    filter_all: ContentFilter,
+    filter_movies: ContentFilter,
+    filter_music: ContentFilter,
+    filter_photos: ContentFilter,
+    filter_television: ContentFilter,
     #[serde(rename = "Server")]
     servers: Option<Vec<UserServer>>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl User {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub fn get_thumb(&self) -> &str {
+        &self.thumb
+    }
+    pub const fn get_protected(&self) -> bool {
+        self.protected
+    }
+    pub const fn get_home(&self) -> bool {
+        self.home
+    }
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub const fn get_allow_camera_upload(&self) -> bool {
+        self.allow_camera_upload
+    }
+    pub const fn get_allow_channels(&self) -> bool {
+        self.allow_channels
+    }
+    pub const fn get_allow_tuners(&self) -> &AllowTuners {
+        &self.allow_tuners
+    }
+    pub const fn get_allow_subtitle_admin(&self) -> bool {
+        self.allow_subtitle_admin
+    }
+    pub const fn get_restricted(&self) -> bool {
+        self.restricted
+    }
+    pub const fn get_filter_all(&self) -> &ContentFilter {
+        &self.filter_all
+    }
+    pub const fn get_filter_movies(&self) -> &ContentFilter {
+        &self.filter_movies
+    }
+    pub const fn get_filter_music(&self) -> &ContentFilter {
+        &self.filter_music
+    }
+    pub const fn get_filter_photos(&self) -> &ContentFilter {
+        &self.filter_photos
+    }
+    pub const fn get_filter_television(&self) -> &ContentFilter {
+        &self.filter_television
+    }
+    pub const fn get_servers(&self) -> &Option<Vec<UserServer>> {
+        &self.servers
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
-struct UserServer {
+pub struct UserServer {
     id: u32,
     server_id: u32,
     machine_identifier: String,
@@ -69,6 +312,38 @@ struct UserServer {
     owned: bool,
     #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
     pending: bool,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl UserServer {
+    pub const fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub const fn get_server_id(&self) -> u32 {
+        self.server_id
+    }
+    pub fn get_machine_identifier(&self) -> &str {
+        &self.machine_identifier
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub const fn get_last_seen_at(&self) -> DateTime<Utc> {
+        self.last_seen_at
+    }
+    pub const fn get_num_libraries(&self) -> u32 {
+        self.num_libraries
+    }
+    pub const fn get_all_libraries(&self) -> bool {
+        self.all_libraries
+    }
+    pub const fn get_owned(&self) -> bool {
+        self.owned
+    }
+    pub const fn get_pending(&self) -> bool {
+        self.pending
+    }
 }
 
 impl UsersMediaContainer {
@@ -78,4 +353,11 @@ impl UsersMediaContainer {
     pub const fn get_users(&self) -> &Vec<User> {
         &self.users
     }
+
+    pub fn get_machine_identifier(&self) -> &str {
+        &self.machine_identifier
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
@@ -1,52 +1,110 @@
-use crate::{
-    http::base_headers,
-    http::get_http_client,
-    my_plex::{MyPlexAccount, MyPlexApiErrorResponse},
-};
+use crate::{my_plex::MyPlexAccount, ClientBuilder, Format, PlexClient, Response};
 
-const MYPLEX_LOGIN_URL: &str = "https://plex.tv/api/v2/users/signin";
-const MYPLEX_ACCOUNT_INFO_URL: &str = "https://plex.tv/api/v2/user?includeSubscriptions=1";
+const MYPLEX_LOGIN_PATH: &str = "api/v2/users/signin";
+const MYPLEX_ACCOUNT_INFO_PATH: &str = "api/v2/user?includeSubscriptions=1";
 
 impl MyPlexAccount {
     /// Log in to [MyPlex](http://app.plex.tv) using username and password.
     pub async fn login(username: &str, password: &str) -> crate::Result<Self> {
-        let params = [
-            ("login", username),
-            ("password", password),
-            ("mememberMe", "true"),
-        ];
-
-        let rb: reqwest::RequestBuilder = { get_http_client()?.post(MYPLEX_LOGIN_URL) };
-        let response = rb
-            .form(&params)
-            .headers(base_headers()?)
-            .header(reqwest::header::ACCEPT, "application/json")
+        Self::login_with_client(&ClientBuilder::default().build()?, username, password).await
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using username and password, communicating
+    /// through the provided client.
+    pub async fn login_with_client(
+        client: &PlexClient,
+        username: &str,
+        password: &str,
+    ) -> crate::Result<Self> {
+        Self::sign_in(
+            client,
+            &[
+                ("login", username),
+                ("password", password),
+                ("rememberMe", "true"),
+            ],
+        )
+        .await
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using username, password and the verification code
+    /// of two-factor authentication. The code is required when [`login`] fails with
+    /// [`PlexApiError::OtpRequired`].
+    ///
+    /// [`login`]: #method.login
+    /// [`PlexApiError::OtpRequired`]: enum.PlexApiError.html#variant.OtpRequired
+    pub async fn login_with_otp(username: &str, password: &str, code: &str) -> crate::Result<Self> {
+        Self::login_with_otp_with_client(
+            &ClientBuilder::default().build()?,
+            username,
+            password,
+            code,
+        )
+        .await
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using username, password and the verification code,
+    /// communicating through the provided client.
+    pub async fn login_with_otp_with_client(
+        client: &PlexClient,
+        username: &str,
+        password: &str,
+        code: &str,
+    ) -> crate::Result<Self> {
+        Self::sign_in(
+            client,
+            &[
+                ("login", username),
+                ("password", password),
+                ("verificationCode", code),
+                ("rememberMe", "true"),
+            ],
+        )
+        .await
+    }
+
+    async fn sign_in(client: &PlexClient, params: &[(&str, &str)]) -> crate::Result<Self> {
+        let response = client
+            .request(
+                reqwest::Method::POST,
+                &format!("{}{}", client.get_my_plex_url(), MYPLEX_LOGIN_PATH),
+            )
+            .form(params)
+            // api/v2 endpoints only answer in JSON
+            .accept(Format::Json)
             .send()
             .await?;
-        MyPlexAccount::handle_login(response).await
+        MyPlexAccount::handle_login(client, response)
     }
 
     /// Log in to [MyPlex](http://app.plex.tv) using existing authentication token.
     pub async fn by_token(auth_token: &str) -> crate::Result<Self> {
-        let rb: reqwest::RequestBuilder = { get_http_client()?.get(MYPLEX_ACCOUNT_INFO_URL) };
-        let response = rb
-            .headers(base_headers()?)
-            .header(reqwest::header::ACCEPT, "application/json")
+        Self::by_token_with_client(&ClientBuilder::default().build()?, auth_token).await
+    }
+
+    /// Log in to [MyPlex](http://app.plex.tv) using existing authentication token, communicating
+    /// through the provided client.
+    pub async fn by_token_with_client(
+        client: &PlexClient,
+        auth_token: &str,
+    ) -> crate::Result<Self> {
+        let response = client
+            .request(
+                reqwest::Method::GET,
+                &format!("{}{}", client.get_my_plex_url(), MYPLEX_ACCOUNT_INFO_PATH),
+            )
+            // api/v2 endpoints only answer in JSON
+            .accept(Format::Json)
             .header("X-Plex-Token", auth_token)
             .send()
             .await?;
 
-        MyPlexAccount::handle_login(response).await
+        MyPlexAccount::handle_login(client, response)
     }
 
-    async fn handle_login(r: reqwest::Response) -> crate::Result<Self> {
-        match r.status() {
-            reqwest::StatusCode::OK | reqwest::StatusCode::CREATED => {
-                Ok(r.json::<MyPlexAccount>().await?)
-            }
-            _ => Err(core::convert::From::from(
-                r.json::<MyPlexApiErrorResponse>().await?,
-            )),
-        }
+    fn handle_login(client: &PlexClient, r: Response) -> crate::Result<Self> {
+        let mut account = r.decode::<MyPlexAccount>()?;
+        account.client = Some(client.clone());
+        Ok(account)
     }
 }
@@ -1,5 +1,4 @@
-use crate::{my_plex::MyPlexAccount, InternalHttpApi, PlexApiError, Result};
-use reqwest::StatusCode;
+use crate::{my_plex::MyPlexAccount, CanMakeRequests, Format, Result};
 
 const CLAIM_TOKEN_URL: &str = "api/claim/token.json";
 
@@ -9,25 +8,19 @@ struct SuccessResponse {
     token: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-struct ErrorResponse {
-    error: String,
-}
-
 impl MyPlexAccount {
     /// Returns a token which can help you to claim freshly installed Plex Server.
     ///
     /// See [https://www.plex.tv/claim](https://www.plex.tv/claim/),
     /// [docker:plexinc/pms-docker](https://hub.docker.com/r/plexinc/pms-docker/).
     pub async fn get_claim_token(&self) -> Result<String> {
-        let response = self.get(CLAIM_TOKEN_URL).await?;
-        match response.status() {
-            StatusCode::OK => Ok((response.json::<SuccessResponse>()).await?.token),
-            _ => {
-                let error = response.json::<ErrorResponse>().await?;
-                Err(PlexApiError::FailedToGetClaimToken(error.error))
-            }
-        }
+        let response: SuccessResponse = self
+            .prepare_query(CLAIM_TOKEN_URL, reqwest::Method::GET)?
+            // api/claim/token.json only answers in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()?;
+        Ok(response.token)
     }
 }
@@ -0,0 +1,87 @@
+use crate::media_container::{HomeUser, HomeUsersMediaContainer, RestrictionProfile};
+use crate::{my_plex::MyPlexAccount, CanMakeRequests, Format, HasPlexClient, PlexApiError, Result};
+
+const HOME_USERS_URL: &str = "api/home/users";
+const MANAGED_HOME_USERS_URL: &str = "api/v2/home/users/restricted";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwitchedUser {
+    authentication_token: String,
+}
+
+impl MyPlexAccount {
+    /// Returns members of the Plex Home, including the current account.
+    pub async fn get_home_users(&self) -> Result<Vec<HomeUser>> {
+        let mc: HomeUsersMediaContainer = self
+            .prepare_query(HOME_USERS_URL, reqwest::Method::GET)?
+            // The home endpoints ignore Accept and always answer in XML
+            .accept(Format::Xml)
+            .send()
+            .await?
+            .decode()?;
+        Ok(mc.get_users().to_owned())
+    }
+
+    /// Switches to the Home user, returning the account with the user's token. The PIN is
+    /// required if the user is [`protected`].
+    ///
+    /// [`protected`]: struct.HomeUser.html#method.get_protected
+    pub async fn switch_home_user(
+        &self,
+        user: &HomeUser,
+        pin: Option<&str>,
+    ) -> Result<MyPlexAccount> {
+        let params: Vec<(&str, &str)> = pin.map(|pin| ("pin", pin)).into_iter().collect();
+        let switched: SwitchedUser = self
+            .prepare_query(
+                format!("{}/{}/switch", HOME_USERS_URL, user.get_id()).as_str(),
+                reqwest::Method::POST,
+            )?
+            .form(&params)
+            // The home endpoints ignore Accept and always answer in XML
+            .accept(Format::Xml)
+            .send()
+            .await?
+            .decode()?;
+
+        MyPlexAccount::by_token_with_client(self.require_client()?, &switched.authentication_token)
+            .await
+    }
+
+    /// Creates a managed Home user, who doesn't have own plex.tv account.
+    pub async fn create_managed_home_user(
+        &self,
+        name: &str,
+        restriction_profile: Option<RestrictionProfile>,
+    ) -> Result<HomeUser> {
+        let mut params = vec![("name", name)];
+        if let Some(profile) = &restriction_profile {
+            params.push(("restrictionProfile", profile.as_str()));
+        }
+
+        self.prepare_query(MANAGED_HOME_USERS_URL, reqwest::Method::POST)?
+            .form(&params)
+            // Managed users are created with api/v2, which only answers in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()
+    }
+
+    /// Removes the user from the Plex Home. Managed users are deleted completely.
+    pub async fn remove_home_user(&self, user: &HomeUser) -> Result<()> {
+        let response = self
+            .prepare_query(
+                format!("{}/{}", HOME_USERS_URL, user.get_id()).as_str(),
+                reqwest::Method::DELETE,
+            )?
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(PlexApiError::from_response(&response))
+        }
+    }
+}
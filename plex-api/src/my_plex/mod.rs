@@ -1,43 +1,237 @@
 mod auth;
 pub(crate) mod claim_token;
 mod devices;
+mod home;
+mod pin;
 mod privacy;
+mod profile;
 mod resources;
+mod session;
+mod sharing;
 mod users;
 mod webhooks;
 
-use crate::{http::base_headers, HasPlexHeaders, PlexApiError, Result};
+pub use pin::Pin;
+pub use privacy::{Metric, Privacy};
+pub use session::Session;
+pub use sharing::SharingSettings;
+
+use crate::{config, HasPlexClient, HasPlexHeaders, PlexClient, RateLimiter, Result};
 use chrono::DateTime;
 use chrono::Utc;
 use reqwest::header::HeaderMap;
-use serde_repr::Deserialize_repr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 use std::collections::HashMap;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
-struct SubscriptionSummary {
+pub struct SubscriptionSummary {
     active: bool,
     #[serde(default)]
     subscribed_at: Option<DateTime<Utc>>,
     status: String,
     payment_service: String,
-    plan: String,
-    features: Vec<String>,
+    plan: SubscriptionPlan,
+    features: Vec<Feature>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl SubscriptionSummary {
+    pub const fn get_active(&self) -> bool {
+        self.active
+    }
+    pub const fn get_subscribed_at(&self) -> Option<DateTime<Utc>> {
+        self.subscribed_at
+    }
+    pub fn get_status(&self) -> &str {
+        &self.status
+    }
+    pub fn get_payment_service(&self) -> &str {
+        &self.payment_service
+    }
+    pub const fn get_plan(&self) -> &SubscriptionPlan {
+        &self.plan
+    }
+    pub const fn get_features(&self) -> &Vec<Feature> {
+        &self.features
+    }
+
+    /// Returns `true` if the subscription is active and includes the feature.
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.active && self.features.contains(&feature)
+    }
+}
+
+/// Billing period of Plex Pass. Plans, which are unknown to the library, are kept as `Unknown`
+/// with the original value.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(from = "String")]
+pub enum SubscriptionPlan {
+    Monthly,
+    Yearly,
+    Lifetime,
+    Unknown(String),
+}
+
+impl From<String> for SubscriptionPlan {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "monthly" => SubscriptionPlan::Monthly,
+            "yearly" => SubscriptionPlan::Yearly,
+            "lifetime" => SubscriptionPlan::Lifetime,
+            _ => SubscriptionPlan::Unknown(value),
+        }
+    }
+}
+
+impl SubscriptionPlan {
+    /// Returns the value, used by Plex for the plan.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SubscriptionPlan::Monthly => "monthly",
+            SubscriptionPlan::Yearly => "yearly",
+            SubscriptionPlan::Lifetime => "lifetime",
+            SubscriptionPlan::Unknown(value) => value,
+        }
+    }
+}
+
+impl Serialize for SubscriptionPlan {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A feature, provided by the subscription. Features, which are unknown to the library, are
+/// kept as `Unknown` with the original value.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String")]
+pub enum Feature {
+    CameraUpload,
+    CloudSync,
+    ContentFilter,
+    Dvr,
+    HardwareTranscoding,
+    Home,
+    LiveTv,
+    Lyrics,
+    MusicVideos,
+    PremiumMusicMetadata,
+    SessionBandwidthRestrictions,
+    SessionKick,
+    Sync,
+    Trailers,
+    Webhooks,
+    Unknown(String),
+}
+
+impl From<String> for Feature {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "camera_upload" => Feature::CameraUpload,
+            "cloudsync" => Feature::CloudSync,
+            "content_filter" => Feature::ContentFilter,
+            "dvr" => Feature::Dvr,
+            "hardware_transcoding" => Feature::HardwareTranscoding,
+            "home" => Feature::Home,
+            "livetv" => Feature::LiveTv,
+            "lyrics" => Feature::Lyrics,
+            "music_videos" => Feature::MusicVideos,
+            "premium_music_metadata" => Feature::PremiumMusicMetadata,
+            "session_bandwidth_restrictions" => Feature::SessionBandwidthRestrictions,
+            "session_kick" => Feature::SessionKick,
+            "sync" => Feature::Sync,
+            "trailers" => Feature::Trailers,
+            "webhooks" => Feature::Webhooks,
+            _ => Feature::Unknown(value),
+        }
+    }
 }
 
-#[derive(Deserialize_repr, Debug)]
-#[repr(u8)]
-enum AutoSelectSubtitleMode {
-    ManuallySelected = 0,
-    ShownWithForeignAudio = 1,
-    AlwaysEnabled = 2,
+impl Feature {
+    /// Returns the value, used by Plex for the feature.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Feature::CameraUpload => "camera_upload",
+            Feature::CloudSync => "cloudsync",
+            Feature::ContentFilter => "content_filter",
+            Feature::Dvr => "dvr",
+            Feature::HardwareTranscoding => "hardware_transcoding",
+            Feature::Home => "home",
+            Feature::LiveTv => "livetv",
+            Feature::Lyrics => "lyrics",
+            Feature::MusicVideos => "music_videos",
+            Feature::PremiumMusicMetadata => "premium_music_metadata",
+            Feature::SessionBandwidthRestrictions => "session_bandwidth_restrictions",
+            Feature::SessionKick => "session_kick",
+            Feature::Sync => "sync",
+            Feature::Trailers => "trailers",
+            Feature::Webhooks => "webhooks",
+            Feature::Unknown(value) => value,
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
+impl Serialize for Feature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutoSelectSubtitleMode {
+    ManuallySelected,
+    ShownWithForeignAudio,
+    AlwaysEnabled,
+    Unknown(u8),
+}
+
+impl From<u8> for AutoSelectSubtitleMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AutoSelectSubtitleMode::ManuallySelected,
+            1 => AutoSelectSubtitleMode::ShownWithForeignAudio,
+            2 => AutoSelectSubtitleMode::AlwaysEnabled,
+            _ => AutoSelectSubtitleMode::Unknown(value),
+        }
+    }
+}
+
+impl From<&AutoSelectSubtitleMode> for u8 {
+    fn from(value: &AutoSelectSubtitleMode) -> Self {
+        match value {
+            AutoSelectSubtitleMode::ManuallySelected => 0,
+            AutoSelectSubtitleMode::ShownWithForeignAudio => 1,
+            AutoSelectSubtitleMode::AlwaysEnabled => 2,
+            AutoSelectSubtitleMode::Unknown(value) => *value,
+        }
+    }
+}
+
+impl Serialize for AutoSelectSubtitleMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(u8::from(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for AutoSelectSubtitleMode {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> std::result::Result<Self, D::Error> {
+        serde_aux::prelude::deserialize_number_from_string::<u8, D>(d)
+            .map(AutoSelectSubtitleMode::from)
+    }
+}
+
+/// Audio and subtitle preferences of the account, which are used by Plex apps by default. It
+/// can be changed with [`MyPlexAccount::update_profile`].
+///
+/// [`MyPlexAccount::update_profile`]: struct.MyPlexAccount.html#method.update_profile
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
-struct Profile {
+pub struct Profile {
     auto_select_audio: bool,
     auto_select_subtitle: AutoSelectSubtitleMode,
     #[serde(deserialize_with = "serde_aux::prelude::deserialize_bool_from_anything")]
@@ -46,33 +240,130 @@ struct Profile {
     default_subtitle_forced: bool,
     default_audio_language: String,
     default_subtitle_language: String,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Deserialize, Debug)]
+impl Profile {
+    pub const fn get_auto_select_audio(&self) -> bool {
+        self.auto_select_audio
+    }
+    pub const fn get_auto_select_subtitle(&self) -> &AutoSelectSubtitleMode {
+        &self.auto_select_subtitle
+    }
+    pub const fn get_default_subtitle_accessibility(&self) -> bool {
+        self.default_subtitle_accessibility
+    }
+    pub const fn get_default_subtitle_forced(&self) -> bool {
+        self.default_subtitle_forced
+    }
+    pub fn get_default_audio_language(&self) -> &str {
+        &self.default_audio_language
+    }
+    pub fn get_default_subtitle_language(&self) -> &str {
+        &self.default_subtitle_language
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    pub fn set_auto_select_audio(&mut self, auto_select_audio: bool) {
+        self.auto_select_audio = auto_select_audio;
+    }
+    pub fn set_auto_select_subtitle(&mut self, auto_select_subtitle: AutoSelectSubtitleMode) {
+        self.auto_select_subtitle = auto_select_subtitle;
+    }
+    pub fn set_default_subtitle_accessibility(&mut self, default_subtitle_accessibility: bool) {
+        self.default_subtitle_accessibility = default_subtitle_accessibility;
+    }
+    pub fn set_default_subtitle_forced(&mut self, default_subtitle_forced: bool) {
+        self.default_subtitle_forced = default_subtitle_forced;
+    }
+    /// Sets the language of audio, e.g. `en` or `de`.
+    pub fn set_default_audio_language(&mut self, default_audio_language: &str) {
+        self.default_audio_language = default_audio_language.to_owned();
+    }
+    pub fn set_default_subtitle_language(&mut self, default_subtitle_language: &str) {
+        self.default_subtitle_language = default_subtitle_language.to_owned();
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
-struct Subscription {
+pub struct Subscription {
     id: Option<i32>,
-    mode: String,
+    mode: SubscriptionPlan,
     state: String,
     renews_at: Option<DateTime<Utc>>,
     ends_at: Option<DateTime<Utc>>,
     #[serde(rename = "type")]
     subscription_type: Option<String>,
     transfer: Option<bool>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl Subscription {
+    pub const fn get_id(&self) -> Option<i32> {
+        self.id
+    }
+    pub const fn get_mode(&self) -> &SubscriptionPlan {
+        &self.mode
+    }
+    pub fn get_state(&self) -> &str {
+        &self.state
+    }
+    pub const fn get_renews_at(&self) -> Option<DateTime<Utc>> {
+        self.renews_at
+    }
+    pub const fn get_ends_at(&self) -> Option<DateTime<Utc>> {
+        self.ends_at
+    }
+    pub const fn get_subscription_type(&self) -> &Option<String> {
+        &self.subscription_type
+    }
+    pub const fn get_transfer(&self) -> Option<bool> {
+        self.transfer
+    }
+
+    /// Returns `true` if the subscription is paid and its features are available.
+    pub fn is_active(&self) -> bool {
+        self.state == "active"
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-struct Service {
+pub struct Service {
     identifier: String,
     endpoint: String,
     token: Option<String>,
     secret: Option<String>,
     status: String,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl Service {
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    pub fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+    pub const fn get_token(&self) -> &Option<String> {
+        &self.token
+    }
+    pub const fn get_secret(&self) -> &Option<String> {
+        &self.secret
+    }
+    pub fn get_status(&self) -> &str {
+        &self.status
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct MyPlexAccount {
@@ -118,33 +409,10 @@ pub struct MyPlexAccount {
     #[serde(with = "chrono::serde::ts_seconds_option")]
     ads_consent_reminder_at: Option<DateTime<Utc>>,
     anonymous: Option<bool>,
-}
-
-#[derive(Deserialize, Debug, Clone)]
-#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-struct MyPlexApiError {
-    code: i32,
-    message: String,
-}
-
-#[derive(Deserialize, Debug, Clone)]
-#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-struct MyPlexApiErrorResponse {
-    errors: Vec<MyPlexApiError>,
-}
-
-impl From<MyPlexApiErrorResponse> for PlexApiError {
-    fn from(r: MyPlexApiErrorResponse) -> Self {
-        let mut errors = vec![];
-        for err in r.errors {
-            errors.push(PlexApiError::MyPlexApiError {
-                code: err.code,
-                message: err.message,
-            })
-        }
-
-        PlexApiError::MyPlexErrorResponse { errors }
-    }
+    #[serde(skip)]
+    client: Option<PlexClient>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
 pub(crate) trait HasMyPlexToken {
@@ -154,16 +422,17 @@ pub(crate) trait HasMyPlexToken {
 
 impl<T> HasPlexHeaders for T
 where
-    T: HasMyPlexToken,
+    T: HasMyPlexToken + HasPlexClient,
 {
     fn headers(&self) -> Result<HeaderMap> {
-        let mut headers = base_headers()?;
+        let client = self.require_client()?;
+        let mut headers = client.get_headers().clone();
 
         if !self.get_auth_token().is_empty() {
             headers.insert("X-Plex-Token", self.get_auth_token().parse()?);
         }
 
-        headers.insert("Accept", "application/json".parse()?);
+        headers.insert("Accept", client.get_format().get_mime_type().parse()?);
 
         Ok(headers)
     }
@@ -181,15 +450,160 @@ impl HasMyPlexToken for MyPlexAccount {
     }
 }
 
+impl HasPlexClient for MyPlexAccount {
+    fn get_client(&self) -> Option<&PlexClient> {
+        MyPlexAccount::get_client(self)
+    }
+}
+
 impl MyPlexAccount {
     /// Return username which was used to log in to MyPlex.
     pub fn get_username(&self) -> String {
         self.username.clone()
     }
+
+    /// Returns the client, which is used for communicating with MyPlex. It's not set for
+    /// an account, which was deserialized manually instead of being obtained with [`login`] or
+    /// [`by_token`].
+    ///
+    /// [`login`]: #method.login
+    /// [`by_token`]: #method.by_token
+    pub fn get_client(&self) -> Option<&PlexClient> {
+        self.client.as_ref()
+    }
+
+    /// Limits requests to MyPlex, made by this account and devices obtained from it.
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        if let Some(client) = self.client.as_mut() {
+            *client = client.with_rate_limiter(rate_limiter);
+        }
+    }
+
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    pub fn get_uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub fn get_email(&self) -> &str {
+        &self.email
+    }
+    pub fn get_thumb(&self) -> &str {
+        &self.thumb
+    }
+    pub const fn get_locale(&self) -> &Option<String> {
+        &self.locale
+    }
+    pub const fn get_email_only_auth(&self) -> bool {
+        self.email_only_auth
+    }
+    pub const fn get_has_password(&self) -> bool {
+        self.has_password
+    }
+    pub const fn get_cloud_sync_device(&self) -> &Option<String> {
+        &self.cloud_sync_device
+    }
+    pub fn get_mailing_list_status(&self) -> &str {
+        &self.mailing_list_status
+    }
+    pub const fn get_mailing_list_active(&self) -> bool {
+        self.mailing_list_active
+    }
+    pub fn get_scrobble_types(&self) -> &str {
+        &self.scrobble_types
+    }
+    pub const fn get_pin(&self) -> &Option<String> {
+        &self.pin
+    }
+    pub const fn get_subscription(&self) -> &SubscriptionSummary {
+        &self.subscription
+    }
+    /// Returns `true` if the account has active Plex Pass with the feature, so endpoints,
+    /// which require it, can be used.
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.subscription.has_feature(feature)
+    }
+    pub fn get_subscription_description(&self) -> &str {
+        &self.subscription_description
+    }
+    pub const fn get_restricted(&self) -> bool {
+        self.restricted
+    }
+    pub const fn get_home(&self) -> bool {
+        self.home
+    }
+    pub const fn get_guest(&self) -> bool {
+        self.guest
+    }
+    pub const fn get_queue_email(&self) -> &Option<String> {
+        &self.queue_email
+    }
+    pub const fn get_queue_uid(&self) -> &Option<HashMap<String, String>> {
+        &self.queue_uid
+    }
+    pub const fn get_home_size(&self) -> i32 {
+        self.home_size
+    }
+    pub const fn get_max_home_size(&self) -> i32 {
+        self.max_home_size
+    }
+    pub const fn get_certificate_version(&self) -> i32 {
+        self.certificate_version
+    }
+    pub const fn get_remember_expires_at(&self) -> DateTime<Utc> {
+        self.remember_expires_at
+    }
+    pub const fn get_profile(&self) -> &Profile {
+        &self.profile
+    }
+    pub const fn get_entitlements(&self) -> &Vec<String> {
+        &self.entitlements
+    }
+    pub const fn get_roles(&self) -> &Vec<String> {
+        &self.roles
+    }
+    pub const fn get_subscriptions(&self) -> &Vec<Subscription> {
+        &self.subscriptions
+    }
+    pub const fn get_services(&self) -> &Vec<Service> {
+        &self.services
+    }
+    pub const fn get_protected(&self) -> bool {
+        self.protected
+    }
+    pub fn get_country(&self) -> &str {
+        &self.country
+    }
+    pub const fn get_home_admin(&self) -> bool {
+        self.home_admin
+    }
+    pub const fn get_trials(&self) -> &Vec<String> {
+        &self.trials
+    }
+    pub const fn get_ads_consent(&self) -> Option<bool> {
+        self.ads_consent
+    }
+    pub const fn get_ads_consent_set_at(&self) -> Option<DateTime<Utc>> {
+        self.ads_consent_set_at
+    }
+    pub const fn get_ads_consent_reminder_at(&self) -> Option<DateTime<Utc>> {
+        self.ads_consent_reminder_at
+    }
+    pub const fn get_anonymous(&self) -> Option<bool> {
+        self.anonymous
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
 }
 
 impl crate::HasBaseUrl for MyPlexAccount {
     fn get_base_url(&self) -> &str {
-        "https://plex.tv/"
+        self.client
+            .as_ref()
+            .map_or(config::MYPLEX_URL, |c| c.get_my_plex_url())
     }
 }
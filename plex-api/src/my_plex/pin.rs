@@ -0,0 +1,195 @@
+use crate::{
+    my_plex::MyPlexAccount, ClientBuilder, Format, HasPlexClient, PlexApiError, PlexClient, Result,
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const PINS_PATH: &str = "api/v2/pins";
+const AUTH_APP_URL: &str = "https://app.plex.tv/auth#?";
+
+/// A PIN, used to log in the way official apps do: the user opens the [`auth url`] in a browser
+/// and signs in there (with a password, Google/Apple account or 2FA), while the application
+/// polls the PIN until it's linked to the account.
+///
+/// [`auth url`]: #method.get_auth_url
+///
+/// # Examples
+///
+/// ```no_run
+/// use plex_api::Pin;
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() -> plex_api::Result<()> {
+/// let pin = Pin::create().await?;
+/// println!("Open {} to sign in", pin.get_auth_url());
+/// let account = pin.wait_for_login(Duration::from_secs(1)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct Pin {
+    id: u64,
+    code: String,
+    #[serde(default)]
+    product: Option<String>,
+    #[serde(default)]
+    trusted: bool,
+    qr: Option<String>,
+    client_identifier: String,
+    location: Option<Value>,
+    expires_in: u32,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    auth_token: Option<String>,
+    new_registration: Option<bool>,
+    #[serde(skip)]
+    pub(crate) client: Option<PlexClient>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl HasPlexClient for Pin {
+    fn get_client(&self) -> Option<&PlexClient> {
+        self.client.as_ref()
+    }
+}
+
+impl Pin {
+    /// Requests a new PIN from [MyPlex](http://app.plex.tv).
+    pub async fn create() -> Result<Self> {
+        Self::create_with_client(&ClientBuilder::default().build()?).await
+    }
+
+    /// Requests a new PIN, communicating through the provided client. The PIN is bound to the
+    /// client's identifier, so the same client should be used to check it.
+    pub async fn create_with_client(client: &PlexClient) -> Result<Self> {
+        let mut pin: Pin = client
+            .request(
+                reqwest::Method::POST,
+                &format!("{}{}", client.get_my_plex_url(), PINS_PATH),
+            )
+            .form(&[("strong", "true")])
+            // api/v2 endpoints only answer in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()?;
+        pin.client = Some(client.clone());
+        Ok(pin)
+    }
+
+    /// Reloads the PIN, to find out if it was linked to an account.
+    pub async fn refresh(&mut self) -> Result<()> {
+        let client = self.require_client()?.clone();
+        let mut pin: Pin = client
+            .request(
+                reqwest::Method::GET,
+                &format!("{}{}/{}", client.get_my_plex_url(), PINS_PATH, self.id),
+            )
+            // api/v2 endpoints only answer in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()?;
+        pin.client = Some(client);
+        *self = pin;
+        Ok(())
+    }
+
+    /// Polls the PIN with the provided interval, until it's linked to an account or expires.
+    /// Returns [`PlexApiError::PinExpired`] in the latter case.
+    ///
+    /// [`PlexApiError::PinExpired`]: enum.PlexApiError.html#variant.PinExpired
+    pub async fn wait_for_login(mut self, interval: Duration) -> Result<MyPlexAccount> {
+        loop {
+            if let Some(auth_token) = &self.auth_token {
+                return MyPlexAccount::by_token_with_client(self.require_client()?, auth_token)
+                    .await;
+            }
+            if self.is_expired() {
+                return Err(PlexApiError::PinExpired { code: self.code });
+            }
+            tokio::time::sleep(interval).await;
+            match self.refresh().await {
+                // plex.tv forgets the PIN once it expires
+                Err(PlexApiError::NotFound(_)) => {
+                    return Err(PlexApiError::PinExpired { code: self.code })
+                }
+                result => result?,
+            }
+        }
+    }
+
+    /// Returns the url, which should be opened by the user to sign in and link the PIN.
+    pub fn get_auth_url(&self) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("clientID", &self.client_identifier)
+            .append_pair("code", &self.code);
+
+        let product = self
+            .client
+            .as_ref()
+            .and_then(|client| client.get_headers().get("X-Plex-Product"))
+            .and_then(|product| product.to_str().ok());
+        if let Some(product) = product {
+            query.append_pair("context[device][product]", product);
+        }
+
+        format!("{}{}", AUTH_APP_URL, query.finish())
+    }
+
+    /// Returns `true` when the PIN can't be linked anymore.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    pub const fn get_id(&self) -> u64 {
+        self.id
+    }
+    pub fn get_code(&self) -> &str {
+        &self.code
+    }
+    pub const fn get_product(&self) -> &Option<String> {
+        &self.product
+    }
+    pub const fn get_trusted(&self) -> bool {
+        self.trusted
+    }
+    /// Returns the url of a QR-code image, which leads to the linking page.
+    pub const fn get_qr(&self) -> &Option<String> {
+        &self.qr
+    }
+    pub fn get_client_identifier(&self) -> &str {
+        &self.client_identifier
+    }
+    /// Returns approximate location of the client, as it's detected by plex.tv.
+    pub const fn get_location(&self) -> &Option<Value> {
+        &self.location
+    }
+    pub const fn get_expires_in(&self) -> u32 {
+        self.expires_in
+    }
+    pub const fn get_created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+    pub const fn get_expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+    /// Returns the token of the account, once the PIN is linked.
+    pub const fn get_auth_token(&self) -> &Option<String> {
+        &self.auth_token
+    }
+    pub const fn get_new_registration(&self) -> Option<bool> {
+        self.new_registration
+    }
+
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
@@ -1,11 +1,8 @@
-use crate::{
-    my_plex::{MyPlexAccount, MyPlexApiErrorResponse},
-    InternalHttpApi,
-};
-use reqwest::StatusCode;
+use crate::{my_plex::MyPlexAccount, CanMakeRequests, Format, InternalHttpApi};
+use serde_json::Value;
 use std::collections::HashMap;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Privacy {
@@ -14,13 +11,47 @@ pub struct Privacy {
     domain: String,
     base_url: String,
     metrics: Vec<Metric>,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize)]
+impl Privacy {
+    pub const fn get_opt_out_playback(&self) -> bool {
+        self.opt_out_playback
+    }
+    pub const fn get_opt_out_library_stats(&self) -> bool {
+        self.opt_out_library_stats
+    }
+    pub fn get_domain(&self) -> &str {
+        &self.domain
+    }
+    pub fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+    pub const fn get_metrics(&self) -> &Vec<Metric> {
+        &self.metrics
+    }
+    pub const fn get_extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-struct Metric {
+pub struct Metric {
     event: String,
     status: String,
+    #[serde(flatten, deserialize_with = "crate::serde_helpers::extra_fields")]
+    extra: HashMap<String, Value>,
+}
+
+impl Metric {
+    pub fn get_event(&self) -> &str {
+        &self.event
+    }
+    pub fn get_status(&self) -> &str {
+        &self.status
+    }
 }
 
 const PRIVACY_URL: &str = "api/v2/user/privacy";
@@ -28,14 +59,12 @@ const PRIVACY_URL: &str = "api/v2/user/privacy";
 impl MyPlexAccount {
     /// Returns current privacy settings, see [Privacy Preferences on plex.tv](https://www.plex.tv/about/privacy-legal/privacy-preferences/#opd).
     pub async fn get_privacy(&self) -> crate::Result<Privacy> {
-        let response = self.get(PRIVACY_URL).await?;
-        if response.status() == StatusCode::OK {
-            let p: Privacy = response.json().await?;
-            Ok(p)
-        } else {
-            let err: MyPlexApiErrorResponse = response.json().await?;
-            Err(core::convert::From::from(err))
-        }
+        self.prepare_query(PRIVACY_URL, reqwest::Method::GET)?
+            // The privacy endpoint only answers in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()
     }
 
     /// Changes privacy settings, see [Privacy Preferences on plex.tv](https://www.plex.tv/about/privacy-legal/privacy-preferences/#opd).
@@ -0,0 +1,72 @@
+use crate::{
+    my_plex::{MyPlexAccount, Profile},
+    CanMakeRequests, Format, PlexApiError, Result,
+};
+
+const PROFILE_URL: &str = "api/v2/user/profile";
+
+impl MyPlexAccount {
+    /// Saves audio and subtitle preferences on plex.tv. The account's [`profile`] is reloaded
+    /// afterwards, so it reflects the stored values.
+    ///
+    /// [`profile`]: #method.get_profile
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use plex_api::MyPlexAccount;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> plex_api::Result<()> {
+    /// let mut account = MyPlexAccount::by_token("token").await?;
+    /// let mut profile = account.get_profile().clone();
+    /// profile.set_default_audio_language("de");
+    /// account.update_profile(&profile).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_profile(&mut self, profile: &Profile) -> Result<()> {
+        let flag = |value: bool| String::from(if value { "1" } else { "0" });
+        let params = [
+            ("autoSelectAudio", flag(profile.auto_select_audio)),
+            (
+                "autoSelectSubtitle",
+                u8::from(&profile.auto_select_subtitle).to_string(),
+            ),
+            (
+                "defaultSubtitleAccessibility",
+                flag(profile.default_subtitle_accessibility),
+            ),
+            (
+                "defaultSubtitleForced",
+                flag(profile.default_subtitle_forced),
+            ),
+            (
+                "defaultAudioLanguage",
+                profile.default_audio_language.clone(),
+            ),
+            (
+                "defaultSubtitleLanguage",
+                profile.default_subtitle_language.clone(),
+            ),
+        ];
+
+        let response = self
+            .prepare_query(PROFILE_URL, reqwest::Method::PUT)?
+            .form(&params)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(PlexApiError::from_response(&response));
+        }
+
+        self.profile = self
+            .prepare_query(PROFILE_URL, reqwest::Method::GET)?
+            // The profile endpoint only answers in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    my_plex::MyPlexAccount, CanMakeRequests, ClientBuilder, HasMyPlexToken, HasPlexClient,
+    PlexApiError, PlexClient, Result,
+};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+const SIGN_OUT_PATH: &str = "api/v2/users/signout";
+
+/// Everything needed to restore [`MyPlexAccount`] later without asking the user to sign in
+/// again: the token, identifier of the client it was issued to and a summary of the account.
+///
+/// The session contains the authentication token, so it should be stored as securely as
+/// a password.
+///
+/// [`MyPlexAccount`]: struct.MyPlexAccount.html
+///
+/// # Examples
+///
+/// ```no_run
+/// use plex_api::{MyPlexAccount, Session};
+///
+/// # #[tokio::main]
+/// # async fn main() -> plex_api::Result<()> {
+/// let account = MyPlexAccount::login("username", "password").await?;
+/// account.get_session()?.save("session.json")?;
+///
+/// // Later on
+/// let account = Session::load("session.json")?.restore().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    auth_token: String,
+    client_identifier: String,
+    my_plex_url: String,
+    uuid: String,
+    username: String,
+    title: String,
+    email: String,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    remember_expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    /// Reads the session, previously written with [`save`].
+    ///
+    /// [`save`]: #method.save
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data).map_err(From::from)
+    }
+
+    /// Writes the session to the file. On Unix the file is readable by the owner only.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path)?;
+        // The mode is only applied to new files, an existing file may be readable by others
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        file.write_all(&serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    /// Reads the session from the OS keyring, where it was stored by [`save_to_keyring`].
+    ///
+    /// [`save_to_keyring`]: #method.save_to_keyring
+    #[cfg(feature = "keyring")]
+    pub fn load_from_keyring(service: &str, user: &str) -> Result<Self> {
+        let data = keyring::Keyring::new(service, user).get_password()?;
+        serde_json::from_str(&data).map_err(From::from)
+    }
+
+    /// Stores the session in the OS keyring under provided service and user names.
+    #[cfg(feature = "keyring")]
+    pub fn save_to_keyring(&self, service: &str, user: &str) -> Result<()> {
+        keyring::Keyring::new(service, user)
+            .set_password(&serde_json::to_string(self)?)
+            .map_err(From::from)
+    }
+
+    /// Removes the session from the OS keyring.
+    #[cfg(feature = "keyring")]
+    pub fn delete_from_keyring(service: &str, user: &str) -> Result<()> {
+        keyring::Keyring::new(service, user)
+            .delete_password()
+            .map_err(From::from)
+    }
+
+    /// Returns the account, re-validating the token with MyPlex. The client is created with
+    /// the same identifier, which was used to obtain the token.
+    pub async fn restore(&self) -> Result<MyPlexAccount> {
+        let client = ClientBuilder::default()
+            .client_identifier(&self.client_identifier)
+            .my_plex_url(&self.my_plex_url)
+            .build()?;
+        self.restore_with_client(&client).await
+    }
+
+    /// Returns the account, re-validating the token with MyPlex, communicating through
+    /// the provided client.
+    pub async fn restore_with_client(&self, client: &PlexClient) -> Result<MyPlexAccount> {
+        if self.is_expired() {
+            return Err(PlexApiError::SessionExpired {
+                expired_at: self.remember_expires_at,
+            });
+        }
+        MyPlexAccount::by_token_with_client(client, &self.auth_token).await
+    }
+
+    /// Returns `true` when the token isn't valid anymore and the user should sign in again.
+    pub fn is_expired(&self) -> bool {
+        self.remember_expires_at <= Utc::now()
+    }
+
+    pub fn get_auth_token(&self) -> &str {
+        &self.auth_token
+    }
+    pub fn get_client_identifier(&self) -> &str {
+        &self.client_identifier
+    }
+    pub fn get_my_plex_url(&self) -> &str {
+        &self.my_plex_url
+    }
+    pub fn get_uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    pub fn get_email(&self) -> &str {
+        &self.email
+    }
+    pub const fn get_remember_expires_at(&self) -> DateTime<Utc> {
+        self.remember_expires_at
+    }
+}
+
+impl MyPlexAccount {
+    /// Returns the session, which can be saved to restore the account later.
+    pub fn get_session(&self) -> Result<Session> {
+        let client = self.require_client()?;
+        Ok(Session {
+            auth_token: self.get_auth_token().to_owned(),
+            client_identifier: client.get_client_identifier().to_owned(),
+            my_plex_url: client.get_my_plex_url().to_owned(),
+            uuid: self.uuid.clone(),
+            username: self.username.clone(),
+            title: self.title.clone(),
+            email: self.email.clone(),
+            remember_expires_at: self.remember_expires_at,
+        })
+    }
+
+    /// Returns `true` when the token isn't valid anymore and the user should sign in again.
+    pub fn is_expired(&self) -> bool {
+        self.remember_expires_at <= Utc::now()
+    }
+
+    /// Revokes the token on plex.tv, so neither the account nor the saved sessions can be used
+    /// anymore.
+    pub async fn sign_out(self) -> Result<()> {
+        let response = self
+            .prepare_query(SIGN_OUT_PATH, reqwest::Method::DELETE)?
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(PlexApiError::from_response(&response))
+        }
+    }
+}
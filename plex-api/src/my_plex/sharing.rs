@@ -0,0 +1,365 @@
+use crate::media_container::{
+    AllowTuners, ContentFilter, Invite, InvitesMediaContainer, SharedSection, SharedServer,
+    SharedServersMediaContainer, User,
+};
+use crate::{my_plex::MyPlexAccount, CanMakeRequests, Format, PlexApiError, Result};
+use url::form_urlencoded;
+
+const SERVERS_URL: &str = "api/servers";
+const FRIENDS_URL: &str = "api/friends";
+const INVITES_URL: &str = "api/invites/requests";
+const SENT_INVITES_URL: &str = "api/invites/requested";
+
+/// Permissions and content restrictions of a friend on the shared server. To change some of
+/// the friend's settings, create it from the [`User`], to keep the current values of others.
+///
+/// [`User`]: struct.User.html
+///
+/// # Examples
+///
+/// ```
+/// use plex_api::{ContentFilter, SharingSettings};
+///
+/// let settings = SharingSettings::default()
+///     .allow_sync(true)
+///     .filter_movies(ContentFilter::default().allow_content_rating("G"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SharingSettings {
+    allow_sync: bool,
+    allow_camera_upload: bool,
+    allow_channels: bool,
+    allow_tuners: AllowTuners,
+    allow_subtitle_admin: bool,
+    filter_movies: ContentFilter,
+    filter_television: ContentFilter,
+    filter_music: ContentFilter,
+}
+
+impl From<&User> for SharingSettings {
+    fn from(user: &User) -> Self {
+        SharingSettings {
+            allow_sync: user.get_allow_sync(),
+            allow_camera_upload: user.get_allow_camera_upload(),
+            allow_channels: user.get_allow_channels(),
+            allow_tuners: user.get_allow_tuners().clone(),
+            allow_subtitle_admin: user.get_allow_subtitle_admin(),
+            filter_movies: user.get_filter_movies().clone(),
+            filter_television: user.get_filter_television().clone(),
+            filter_music: user.get_filter_music().clone(),
+        }
+    }
+}
+
+impl SharingSettings {
+    pub fn allow_sync(mut self, allow_sync: bool) -> Self {
+        self.allow_sync = allow_sync;
+        self
+    }
+
+    pub fn allow_camera_upload(mut self, allow_camera_upload: bool) -> Self {
+        self.allow_camera_upload = allow_camera_upload;
+        self
+    }
+
+    pub fn allow_channels(mut self, allow_channels: bool) -> Self {
+        self.allow_channels = allow_channels;
+        self
+    }
+
+    pub fn allow_tuners(mut self, allow_tuners: AllowTuners) -> Self {
+        self.allow_tuners = allow_tuners;
+        self
+    }
+
+    pub fn allow_subtitle_admin(mut self, allow_subtitle_admin: bool) -> Self {
+        self.allow_subtitle_admin = allow_subtitle_admin;
+        self
+    }
+
+    pub fn filter_movies(mut self, filter: ContentFilter) -> Self {
+        self.filter_movies = filter;
+        self
+    }
+
+    pub fn filter_television(mut self, filter: ContentFilter) -> Self {
+        self.filter_television = filter;
+        self
+    }
+
+    pub fn filter_music(mut self, filter: ContentFilter) -> Self {
+        self.filter_music = filter;
+        self
+    }
+
+    pub const fn get_allow_sync(&self) -> bool {
+        self.allow_sync
+    }
+    pub const fn get_allow_camera_upload(&self) -> bool {
+        self.allow_camera_upload
+    }
+    pub const fn get_allow_channels(&self) -> bool {
+        self.allow_channels
+    }
+    pub const fn get_allow_tuners(&self) -> &AllowTuners {
+        &self.allow_tuners
+    }
+    pub const fn get_allow_subtitle_admin(&self) -> bool {
+        self.allow_subtitle_admin
+    }
+    pub const fn get_filter_movies(&self) -> &ContentFilter {
+        &self.filter_movies
+    }
+    pub const fn get_filter_television(&self) -> &ContentFilter {
+        &self.filter_television
+    }
+    pub const fn get_filter_music(&self) -> &ContentFilter {
+        &self.filter_music
+    }
+
+    fn to_params(&self) -> Vec<(&'static str, String)> {
+        let flag = |value: bool| String::from(if value { "1" } else { "0" });
+        vec![
+            ("allowSync", flag(self.allow_sync)),
+            ("allowCameraUpload", flag(self.allow_camera_upload)),
+            ("allowChannels", flag(self.allow_channels)),
+            ("allowTuners", u8::from(&self.allow_tuners).to_string()),
+            ("allowSubtitleAdmin", flag(self.allow_subtitle_admin)),
+            ("filterMovies", self.filter_movies.to_string()),
+            ("filterTelevision", self.filter_television.to_string()),
+            ("filterMusic", self.filter_music.to_string()),
+        ]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ServersMediaContainer {
+    #[serde(rename = "Server", default)]
+    servers: Vec<ServerSections>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerSections {
+    #[serde(rename = "Section", default)]
+    sections: Vec<SharedSection>,
+}
+
+impl MyPlexAccount {
+    /// Returns library sections of the owned server, which can be shared with friends.
+    pub async fn get_server_sections(
+        &self,
+        machine_identifier: &str,
+    ) -> Result<Vec<SharedSection>> {
+        let mc: ServersMediaContainer = self
+            .prepare_query(
+                format!("{}/{}", SERVERS_URL, machine_identifier).as_str(),
+                reqwest::Method::GET,
+            )?
+            // The sharing endpoints ignore Accept and always answer in XML
+            .accept(Format::Xml)
+            .send()
+            .await?
+            .decode()?;
+        Ok(mc
+            .servers
+            .into_iter()
+            .flat_map(|server| server.sections)
+            .collect())
+    }
+
+    /// Returns the shares of the owned server, including the pending ones.
+    pub async fn get_shared_servers(&self, machine_identifier: &str) -> Result<Vec<SharedServer>> {
+        let mc: SharedServersMediaContainer = self
+            .prepare_query(
+                format!("{}/{}/shared_servers", SERVERS_URL, machine_identifier).as_str(),
+                reqwest::Method::GET,
+            )?
+            // The sharing endpoints ignore Accept and always answer in XML
+            .accept(Format::Xml)
+            .send()
+            .await?
+            .decode()?;
+        Ok(mc.get_shared_servers().to_owned())
+    }
+
+    /// Invites the user with provided username or email to the server, sharing the sections
+    /// with given [`ids`]. All the sections are shared if the list is empty.
+    ///
+    /// [`ids`]: struct.SharedSection.html#method.get_id
+    pub async fn invite_friend(
+        &self,
+        machine_identifier: &str,
+        username_or_email: &str,
+        section_ids: &[u32],
+        settings: &SharingSettings,
+    ) -> Result<SharedServer> {
+        let sharing_settings: serde_json::Map<String, serde_json::Value> = settings
+            .to_params()
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.into()))
+            .collect();
+        let body = serde_json::json!({
+            "server_id": machine_identifier,
+            "shared_server": {
+                "library_section_ids": section_ids,
+                "invited_email": username_or_email,
+            },
+            "sharing_settings": sharing_settings,
+        });
+
+        let mc: SharedServersMediaContainer = self
+            .prepare_query(
+                format!("{}/{}/shared_servers", SERVERS_URL, machine_identifier).as_str(),
+                reqwest::Method::POST,
+            )?
+            .json(&body)
+            // The sharing endpoints ignore Accept and always answer in XML
+            .accept(Format::Xml)
+            .send()
+            .await?
+            .decode()?;
+        mc.get_shared_servers()
+            .first()
+            .cloned()
+            .ok_or_else(|| PlexApiError::SharedServerMissing {
+                invited: username_or_email.to_owned(),
+            })
+    }
+
+    /// Replaces the sections, shared with a friend. The share's id can be found in
+    /// [`SharedServer`] or [`UserServer`].
+    ///
+    /// [`SharedServer`]: struct.SharedServer.html#method.get_id
+    /// [`UserServer`]: struct.UserServer.html#method.get_id
+    pub async fn update_shared_sections(
+        &self,
+        machine_identifier: &str,
+        shared_server_id: u32,
+        section_ids: &[u32],
+    ) -> Result<()> {
+        let body = serde_json::json!({
+            "server_id": machine_identifier,
+            "shared_server": {
+                "library_section_ids": section_ids,
+            },
+        });
+
+        let response = self
+            .prepare_query(
+                format!(
+                    "{}/{}/shared_servers/{}",
+                    SERVERS_URL, machine_identifier, shared_server_id
+                )
+                .as_str(),
+                reqwest::Method::PUT,
+            )?
+            .json(&body)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(PlexApiError::from_response(&response))
+        }
+    }
+
+    /// Updates permissions and content restrictions of the friend.
+    pub async fn update_sharing_settings(
+        &self,
+        user: &User,
+        settings: &SharingSettings,
+    ) -> Result<()> {
+        let response = self
+            .prepare_query(
+                format!("{}/{}", FRIENDS_URL, user.get_id()).as_str(),
+                reqwest::Method::PUT,
+            )?
+            .form(&settings.to_params())
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(PlexApiError::from_response(&response))
+        }
+    }
+
+    /// Removes the friend, revoking access to all the shared servers.
+    pub async fn remove_friend(&self, user: &User) -> Result<()> {
+        let response = self
+            .prepare_query(
+                format!("{}/{}", FRIENDS_URL, user.get_id()).as_str(),
+                reqwest::Method::DELETE,
+            )?
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(PlexApiError::from_response(&response))
+        }
+    }
+
+    /// Returns the invites, which were sent to the account and are waiting for a response.
+    pub async fn get_pending_invites(&self) -> Result<Vec<Invite>> {
+        self.get_invites(INVITES_URL).await
+    }
+
+    /// Returns the invites, which were sent by the account and weren't accepted yet.
+    pub async fn get_sent_invites(&self) -> Result<Vec<Invite>> {
+        self.get_invites(SENT_INVITES_URL).await
+    }
+
+    pub async fn accept_invite(&self, invite: &Invite) -> Result<()> {
+        self.update_invite(INVITES_URL, invite, reqwest::Method::PUT)
+            .await
+    }
+
+    pub async fn reject_invite(&self, invite: &Invite) -> Result<()> {
+        self.update_invite(INVITES_URL, invite, reqwest::Method::DELETE)
+            .await
+    }
+
+    /// Cancels the invite, which was sent by the account.
+    pub async fn cancel_invite(&self, invite: &Invite) -> Result<()> {
+        self.update_invite(SENT_INVITES_URL, invite, reqwest::Method::DELETE)
+            .await
+    }
+
+    async fn get_invites(&self, url: &str) -> Result<Vec<Invite>> {
+        let mc: InvitesMediaContainer = self
+            .prepare_query(url, reqwest::Method::GET)?
+            // The invites endpoints ignore Accept and always answer in XML
+            .accept(Format::Xml)
+            .send()
+            .await?
+            .decode()?;
+        Ok(mc.get_invites().to_owned())
+    }
+
+    async fn update_invite(
+        &self,
+        url: &str,
+        invite: &Invite,
+        method: reqwest::Method,
+    ) -> Result<()> {
+        let flag = |value: bool| if value { "1" } else { "0" };
+        let params = form_urlencoded::Serializer::new(String::new())
+            .append_pair("friend", flag(invite.get_friend()))
+            .append_pair("server", flag(invite.get_server()))
+            .append_pair("home", flag(invite.get_home()))
+            .finish();
+        let response = self
+            .prepare_query(
+                format!("{}/{}?{}", url, invite.get_id(), params).as_str(),
+                method,
+            )?
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(PlexApiError::from_response(&response))
+        }
+    }
+}
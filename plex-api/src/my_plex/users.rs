@@ -1,23 +1,60 @@
-use crate::media_container::{User, UsersMediaContainer};
-use crate::{
-    my_plex::{MyPlexAccount, MyPlexApiErrorResponse},
-    InternalHttpApi,
-};
-use reqwest::StatusCode;
+use crate::media_container::{User, UserServer, UsersMediaContainer};
+use crate::{my_plex::MyPlexAccount, CanMakeRequests, PlexApiError, SharingSettings};
 
 const USERS_URL: &str = "api/users/";
 
 impl MyPlexAccount {
     /// Returns a list of users, who has access to the current server, except the owner.
     pub async fn get_users(&self) -> crate::Result<Vec<User>> {
-        let response = self.get(USERS_URL).await?;
-        if response.status() == StatusCode::OK {
-            let mc: UsersMediaContainer = quick_xml::de::from_str(response.text().await?.as_str())?;
-            Ok(mc.get_users().to_owned())
-        } else {
-            let err: MyPlexApiErrorResponse =
-                quick_xml::de::from_str(response.text().await?.as_str())?;
-            Err(core::convert::From::from(err))
+        let mc: UsersMediaContainer = self
+            .prepare_query(USERS_URL, reqwest::Method::GET)?
+            .send()
+            .await?
+            .decode()?;
+        Ok(mc.get_users().to_owned())
+    }
+
+    /// Changes permissions of the user on the server, which is shared with them. Returns
+    /// the user with updated permissions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use plex_api::{MyPlexAccount, SharingSettings};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> plex_api::Result<()> {
+    /// let account = MyPlexAccount::by_token("token").await?;
+    /// let user = &account.get_users().await?[0];
+    /// let server = &user.get_servers().as_ref().unwrap()[0];
+    /// let settings = SharingSettings::from(user).allow_sync(true);
+    /// let user = account.update_user_permissions(user, server, &settings).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_user_permissions(
+        &self,
+        user: &User,
+        server: &UserServer,
+        settings: &SharingSettings,
+    ) -> crate::Result<User> {
+        let is_shared = user
+            .get_servers()
+            .iter()
+            .flatten()
+            .any(|s| s.get_id() == server.get_id());
+        if !is_shared {
+            return Err(PlexApiError::ServerNotShared {
+                user_id: user.get_id(),
+                server_id: server.get_id(),
+            });
         }
+
+        self.update_sharing_settings(user, settings).await?;
+        self.get_users()
+            .await?
+            .into_iter()
+            .find(|u| u.get_id() == user.get_id())
+            .ok_or(PlexApiError::UserNotFound { id: user.get_id() })
     }
 }
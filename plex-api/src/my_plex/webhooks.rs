@@ -1,8 +1,4 @@
-use crate::{
-    my_plex::{MyPlexAccount, MyPlexApiErrorResponse},
-    InternalHttpApi, PlexApiError,
-};
-use reqwest::StatusCode;
+use crate::{my_plex::MyPlexAccount, CanMakeRequests, Format, InternalHttpApi, PlexApiError};
 
 const WEBHOOKS_URL: &str = "api/v2/user/webhooks";
 
@@ -15,18 +11,14 @@ struct Webhook {
 impl MyPlexAccount {
     /// Returns a list of URLs for currently registered WebHooks.
     pub async fn get_webhooks(&self) -> crate::Result<Vec<String>> {
-        let response = self.get(WEBHOOKS_URL).await?;
-        if response.status() == StatusCode::OK {
-            let hooks: Vec<Webhook> = serde_json::from_str(response.text().await?.as_str())?;
-            let mut ret: Vec<String> = Vec::new();
-            for hook in hooks {
-                ret.push(hook.url)
-            }
-            Ok(ret)
-        } else {
-            let err: MyPlexApiErrorResponse = response.json().await?;
-            Err(core::convert::From::from(err))
-        }
+        let hooks: Vec<Webhook> = self
+            .prepare_query(WEBHOOKS_URL, reqwest::Method::GET)?
+            // Webhooks are only available in JSON
+            .accept(Format::Json)
+            .send()
+            .await?
+            .decode()?;
+        Ok(hooks.into_iter().map(|hook| hook.url).collect())
     }
 
     /// Sets a list of WebHooks to provided URLs list.
@@ -38,11 +30,10 @@ impl MyPlexAccount {
         };
 
         let response = self.post_form(WEBHOOKS_URL, &params).await?;
-        if response.status() == StatusCode::CREATED {
+        if response.status().is_success() {
             Ok(())
         } else {
-            let err: MyPlexApiErrorResponse = response.json().await?;
-            Err(core::convert::From::from(err))
+            Err(PlexApiError::from_response(&response))
         }
     }
 
@@ -1,4 +1,7 @@
 pub use crate::error::PlexApiError;
+pub use crate::http::{
+    ClientBuilder, DiskCache, InMemoryCache, PlexClient, RateLimit, RateLimiter, RetryPolicy,
+};
 pub use crate::media_container::SettingValue;
 pub use crate::my_plex::MyPlexAccount;
 pub use crate::server::Server;
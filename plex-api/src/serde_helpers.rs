@@ -4,10 +4,34 @@ use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
 use serde::de::{self, Deserializer, Visitor};
-use serde::Deserialize;
+use serde::{Deserialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::fmt;
 use std::iter::FromIterator;
 
+/// Collects attributes, which are unknown to the model, so a new attribute added by Plex doesn't
+/// break deserialization. With `test_new_attributes` feature they're reported as an error instead,
+/// to find out what should be added to the models.
+pub(crate) fn extra_fields<'de, D>(deserializer: D) -> Result<HashMap<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let extra = HashMap::<String, Value>::deserialize(deserializer)?;
+
+    #[cfg(all(test, feature = "test_new_attributes"))]
+    if !extra.is_empty() {
+        let mut keys: Vec<&str> = extra.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        return Err(de::Error::custom(format!(
+            "unknown fields: {}",
+            keys.join(", ")
+        )));
+    }
+
+    Ok(extra)
+}
+
 pub(crate) fn option_bool_from_anything<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
 where
     D: Deserializer<'de>,
@@ -113,3 +137,96 @@ impl serde_with::Separator for PipeSeparator {
         "|"
     }
 }
+
+pub(crate) fn serialize_duration<S>(value: &chrono::Duration, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_i64(value.num_milliseconds())
+}
+
+pub(crate) fn serialize_option_duration<S>(
+    value: &Option<chrono::Duration>,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serialize_duration(value, s),
+        None => s.serialize_none(),
+    }
+}
+
+#[allow(deprecated)]
+pub(crate) fn serialize_option_date<S>(
+    value: &Option<chrono::Date<Utc>>,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => s.serialize_str(&value.format("%Y-%m-%d").to_string()),
+        None => s.serialize_none(),
+    }
+}
+
+/// Serializes the value as a string, `None` is written as an empty string.
+pub(crate) fn serialize_option_to_string<S, V>(value: &Option<V>, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Display,
+{
+    match value {
+        Some(value) => s.serialize_str(&value.to_string()),
+        None => s.serialize_str(""),
+    }
+}
+
+fn serialize_option_separated<S, V>(
+    value: &Option<Vec<V>>,
+    separator: &str,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Display,
+{
+    match value {
+        Some(values) => s.serialize_str(
+            &values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(separator),
+        ),
+        None => s.serialize_none(),
+    }
+}
+
+pub(crate) fn serialize_option_comma_separated<S, V>(
+    value: &Option<Vec<V>>,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Display,
+{
+    serialize_option_separated(value, ",", s)
+}
+
+pub(crate) fn serialize_option_pipe_separated<S, V>(
+    value: &Option<Vec<V>>,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Display,
+{
+    serialize_option_separated(
+        value,
+        <PipeSeparator as serde_with::Separator>::separator(),
+        s,
+    )
+}
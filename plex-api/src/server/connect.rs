@@ -1,7 +1,6 @@
-use crate::http::{base_headers, get_http_client};
 use crate::media_container::ServerMediaContainer;
 use crate::server::Server;
-use crate::{PlexApiError, Result};
+use crate::{ClientBuilder, HasBaseUrl, HasMyPlexToken, HasPlexClient, PlexClient, Result};
 
 impl Server {
     /// Establish a connection with the server server by provided url and [`authentication token`].
@@ -12,28 +11,35 @@ impl Server {
         url: U,
         auth_token: &str,
     ) -> Result<Self> {
-        let rb: reqwest::RequestBuilder = { get_http_client()?.get(url.as_str()) };
-        let response = rb
-            .headers(base_headers()?)
+        Self::connect_with_client(&ClientBuilder::default().build()?, url, auth_token).await
+    }
+
+    /// Establish a connection with the server, same as [`connect`], but communicating through
+    /// the provided client.
+    ///
+    /// [`connect`]: #method.connect
+    pub async fn connect_with_client<U: reqwest::IntoUrl + crate::AsStr + Send>(
+        client: &PlexClient,
+        url: U,
+        auth_token: &str,
+    ) -> Result<Self> {
+        let mc: ServerMediaContainer = client
+            .request(reqwest::Method::GET, url.as_str())
             .header("X-Plex-Token", auth_token)
+            .accept(client.get_format())
             .send()
-            .await?;
-
-        if response.status() == reqwest::StatusCode::OK {
-            let mc: ServerMediaContainer =
-                quick_xml::de::from_str(response.text().await?.as_str())?;
-            Ok(Server {
-                info: mc,
-                url: url.into_url()?,
-                auth_token: String::from(auth_token),
-            })
-        } else {
-            Err(PlexApiError::UnexpectedApiResponse(response.text().await?))
-        }
+            .await?
+            .decode()?;
+        Ok(Server::new(mc, url.into_url()?, auth_token, client))
     }
 
     pub async fn refresh(&mut self) -> Result<()> {
-        let new_server = Server::connect(&self.url.as_str().to_string(), &self.auth_token).await;
+        let new_server = Server::connect_with_client(
+            self.require_client()?,
+            self.get_base_url(),
+            self.get_auth_token(),
+        )
+        .await;
         match new_server {
             Ok(srv) => {
                 *self = srv;
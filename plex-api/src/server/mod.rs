@@ -23,48 +23,125 @@ mod connect;
 mod my_plex;
 mod settings;
 
-use crate::library::Library;
-use crate::{media_container::ServerMediaContainer, HasBaseUrl, HasMyPlexToken, Result};
+use crate::library::{Library, MetadataStream, Pagination, LIBRARY_RECENTLY_ADDED_URL};
+use crate::{
+    media_container::ServerMediaContainer, HasBaseUrl, HasMyPlexToken, HasPlexClient, PlexClient,
+    RateLimiter, Result,
+};
 use semver::Version;
+use std::sync::Arc;
 use url::Url;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[cfg_attr(all(test, feature = "test_new_attributes"), serde(deny_unknown_fields))]
-pub struct Server {
+struct ServerInner {
     info: ServerMediaContainer,
     url: Url,
     auth_token: String,
+    #[serde(skip)]
+    client: Option<PlexClient>,
+}
+
+/// A connection to Plex Media Server.
+///
+/// Clones are cheap and share the connection details, so the server (as well as every
+/// [`Library`] obtained from it) might be moved to another task.
+///
+/// [`Library`]: struct.Library.html
+#[derive(Deserialize, Debug, Clone)]
+#[serde(from = "ServerInner")]
+pub struct Server {
+    inner: Arc<ServerInner>,
+}
+
+impl From<ServerInner> for Server {
+    fn from(inner: ServerInner) -> Self {
+        Server {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl serde::Serialize for Server {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize(serializer)
+    }
 }
 
-impl<'a> Server {
-    pub const fn get_version(&self) -> &Version {
-        self.info.get_version()
+impl Server {
+    pub(crate) fn new(
+        info: ServerMediaContainer,
+        url: Url,
+        auth_token: &str,
+        client: &PlexClient,
+    ) -> Self {
+        Server::from(ServerInner {
+            info,
+            url,
+            auth_token: String::from(auth_token),
+            client: Some(client.clone()),
+        })
+    }
+    pub fn get_version(&self) -> &Version {
+        self.inner.info.get_version()
     }
-    pub async fn get_sections(&'a self) -> Result<Library<'a>> {
+    pub async fn get_sections(&self) -> Result<Library> {
         Library::sections(self).await
     }
-    pub async fn get_recently_added(&'a self) -> Result<Library<'a>> {
+    pub async fn get_recently_added(&self) -> Result<Library> {
         Library::recently_added(self).await
     }
-    pub async fn get_on_deck(&'a self) -> Result<Library<'a>> {
+    pub async fn get_on_deck(&self) -> Result<Library> {
         Library::on_deck(self).await
     }
+    /// Returns a stream of the items from any container with metadata, e.g.
+    /// `library/sections/1/all`, loaded page by page.
+    pub fn stream_metadata(&self, path: &str, pagination: Pagination) -> MetadataStream {
+        Library::stream(self, path, pagination)
+    }
+    /// Returns a stream of the recently added items, loaded page by page.
+    pub fn stream_recently_added(&self, pagination: Pagination) -> MetadataStream {
+        self.stream_metadata(LIBRARY_RECENTLY_ADDED_URL, pagination)
+    }
+    /// Returns the client, which is used for communicating with the server. It's not set for
+    /// a server, which was deserialized manually instead of being obtained with [`connect`].
+    ///
+    /// [`connect`]: #method.connect
+    pub fn get_client(&self) -> Option<&PlexClient> {
+        self.inner.client.as_ref()
+    }
+    /// Limits requests to this server, without affecting other users of the client and other
+    /// clones of the server.
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        if let Some(client) = Arc::make_mut(&mut self.inner).client.as_mut() {
+            *client = client.with_rate_limiter(rate_limiter);
+        }
+    }
 }
 
 impl HasMyPlexToken for Server {
     /// Returns authentication token for current server.
     fn get_auth_token(&self) -> &str {
-        &self.auth_token
+        &self.inner.auth_token
     }
 
     /// Sets authentication token for current server.
     fn set_auth_token(&mut self, auth_token: &str) {
-        self.auth_token = String::from(auth_token);
+        Arc::make_mut(&mut self.inner).auth_token = String::from(auth_token);
+    }
+}
+
+impl HasPlexClient for Server {
+    fn get_client(&self) -> Option<&PlexClient> {
+        Server::get_client(self)
     }
 }
 
 impl HasBaseUrl for Server {
     fn get_base_url(&self) -> &str {
-        self.url.as_str()
+        self.inner.url.as_str()
     }
 }
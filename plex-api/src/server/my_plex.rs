@@ -15,10 +15,10 @@ impl Server {
             .send()
             .await?;
 
-        if response.status() == 200 {
+        if response.status().is_success() {
             self.refresh().await
         } else {
-            Err(PlexApiError::UnexpectedUnclaimError(response.text().await?))
+            Err(PlexApiError::from_response(&response))
         }
     }
 
@@ -41,10 +41,10 @@ impl Server {
             .send()
             .await?;
 
-        if response.status() == 200 {
+        if response.status().is_success() {
             self.refresh().await
         } else {
-            Err(PlexApiError::UnexpectedClaimError(response.text().await?))
+            Err(PlexApiError::from_response(&response))
         }
     }
 }
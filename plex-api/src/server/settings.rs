@@ -1,6 +1,5 @@
 use crate::{
     CanMakeRequests, InternalHttpApi, PlexApiError, Result, Server, SettingsMediaContainer,
-    SettingsMediaContainerOuter,
 };
 use url::form_urlencoded;
 
@@ -8,14 +7,7 @@ const SETTINGS_URL: &str = ":/prefs";
 
 impl Server {
     pub async fn get_settings(&self) -> Result<SettingsMediaContainer> {
-        let response = self.get(SETTINGS_URL).await?;
-        if response.status() == reqwest::StatusCode::OK {
-            Ok(SettingsMediaContainer::from(
-                response.json::<SettingsMediaContainerOuter>().await?,
-            ))
-        } else {
-            Err(PlexApiError::UnexpectedApiResponse(response.text().await?))
-        }
+        self.get(SETTINGS_URL).await?.decode()
     }
 
     pub async fn update_settings(&self, settings: &SettingsMediaContainer) -> Result<()> {
@@ -38,7 +30,7 @@ impl Server {
             if response.status() == 200 {
                 Ok(())
             } else {
-                Err(PlexApiError::UnexpectedApiResponse(response.text().await?))
+                Err(PlexApiError::from_response(&response))
             }
         }
     }
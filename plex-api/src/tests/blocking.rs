@@ -0,0 +1,58 @@
+use crate::blocking::{MyPlexAccount, Server};
+use crate::{ClientBuilder, Pagination, PlexClient, Result};
+use plex_mock::MockServer;
+use tokio::runtime::Runtime;
+
+/// The mock needs a runtime of its own, which keeps serving requests from a background thread.
+fn start_mock() -> (Runtime, MockServer) {
+    let runtime = Runtime::new().unwrap();
+    let mock = runtime.block_on(MockServer::start()).unwrap();
+    (runtime, mock)
+}
+
+fn client_for(mock: &MockServer) -> PlexClient {
+    ClientBuilder::default()
+        .client_identifier("plex-mock-blocking-client")
+        .my_plex_url(&mock.url())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn blocking_account_connects_to_server() {
+    let (_runtime, mock) = start_mock();
+    let account =
+        MyPlexAccount::by_token_with_client(&client_for(&mock), plex_mock::AUTH_TOKEN).unwrap();
+    assert!(!account.get_username().is_empty());
+    assert!(account.get_privacy().is_ok());
+
+    let resources = account.get_resources().unwrap();
+    let device = resources
+        .iter()
+        .find(|d| d.get_name() == "plex-mock")
+        .expect("Mocked server wasn't found in resources");
+
+    let srv = device.connect_to_server();
+    assert!(srv.is_ok(), "Unable to connect to server: {:?}", srv.err());
+}
+
+#[test]
+fn blocking_server_library_and_settings() {
+    let (_runtime, mock) = start_mock();
+    let mut srv =
+        Server::connect_with_client(&client_for(&mock), &mock.url(), plex_mock::AUTH_TOKEN)
+            .unwrap();
+
+    assert!(srv.refresh().is_ok());
+    let sections = srv.get_sections().unwrap();
+    let section = &sections.get_directory().as_ref().unwrap()[0];
+    let items = sections
+        .stream_section_items(section, Pagination::default().page_size(100))
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(plex_mock::SECTION_SIZE as usize, items.len());
+    assert!(srv.get_on_deck().is_ok());
+
+    let settings = srv.get_settings().unwrap();
+    assert!(settings.get("FriendlyName").is_ok());
+}
@@ -0,0 +1,240 @@
+use crate::{
+    ClientBuilder, DiskCache, HttpTransport, InMemoryCache, MyPlexAccount, PlexClient, Request,
+    ReqwestTransport, Response, ResponseCache, Result, Server,
+};
+use async_trait::async_trait;
+use plex_mock::MockServer;
+use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode};
+use std::sync::{Arc, Mutex};
+
+/// Transport, which remembers statuses of the responses, actually received from the network.
+#[derive(Debug, Default, Clone)]
+struct StatusRecorder {
+    inner: ReqwestTransport,
+    statuses: Arc<Mutex<Vec<StatusCode>>>,
+}
+
+impl StatusRecorder {
+    fn statuses(&self) -> Vec<StatusCode> {
+        self.statuses.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for StatusRecorder {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let response = self.inner.execute(request).await?;
+        self.statuses.lock().unwrap().push(response.status());
+        Ok(response)
+    }
+}
+
+/// Transport, which always responds with the provided `Cache-Control` header and body.
+#[derive(Debug, Clone)]
+struct CacheControlTransport {
+    cache_control: &'static str,
+    body: &'static [u8],
+    requests: Arc<Mutex<u32>>,
+}
+
+#[async_trait]
+impl HttpTransport for CacheControlTransport {
+    async fn execute(&self, _request: Request) -> Result<Response> {
+        *self.requests.lock().unwrap() += 1;
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static(self.cache_control));
+        Ok(Response::new(StatusCode::OK, headers, self.body.to_vec()))
+    }
+}
+
+/// Transport, which responds with `304 Not Modified` to any conditional request.
+#[derive(Debug, Clone, Default)]
+struct NotModifiedTransport {
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+#[async_trait]
+impl HttpTransport for NotModifiedTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let conditional = request.get_headers().contains_key(IF_NONE_MATCH);
+        self.requests.lock().unwrap().push(request);
+        if conditional {
+            return Ok(Response::new(
+                StatusCode::NOT_MODIFIED,
+                HeaderMap::new(),
+                vec![],
+            ));
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"fresh\""));
+        Ok(Response::new(StatusCode::OK, headers, b"fresh".to_vec()))
+    }
+}
+
+fn client_for<C: ResponseCache + 'static>(
+    mock: &MockServer,
+    transport: &StatusRecorder,
+    cache: C,
+) -> PlexClient {
+    ClientBuilder::default()
+        .client_identifier("plex-mock-client")
+        .my_plex_url(&mock.url())
+        .transport(transport.clone())
+        .response_cache(cache)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn server_responses_are_revalidated() {
+    let mock = MockServer::start().await.unwrap();
+    let transport = StatusRecorder::default();
+    let client = client_for(&mock, &transport, InMemoryCache::default());
+    let srv = Server::connect_with_client(&client, &mock.url(), plex_mock::AUTH_TOKEN)
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        srv.get_sections().await.expect("Unable to get sections");
+        srv.get_settings().await.expect("Unable to get settings");
+    }
+
+    assert_eq!(
+        vec![
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::NOT_MODIFIED,
+            StatusCode::NOT_MODIFIED
+        ],
+        transport.statuses()
+    );
+}
+
+#[tokio::test]
+async fn disk_cache_is_shared_between_clients() {
+    let mock = MockServer::start().await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut devices = vec![];
+    let mut transports = vec![];
+    for _ in 0..2 {
+        let transport = StatusRecorder::default();
+        let client = client_for(&mock, &transport, DiskCache::new(dir.path()).unwrap());
+        let account = MyPlexAccount::by_token_with_client(&client, plex_mock::AUTH_TOKEN)
+            .await
+            .unwrap();
+        devices.push(account.get_resources().await.unwrap().len());
+        transports.push(transport);
+    }
+
+    assert_eq!(devices[0], devices[1]);
+    assert_eq!(
+        Some(&StatusCode::OK),
+        transports[0].statuses().last(),
+        "The first client should download the resources"
+    );
+    assert_eq!(
+        Some(&StatusCode::NOT_MODIFIED),
+        transports[1].statuses().last(),
+        "The second client should revalidate the resources"
+    );
+    assert!(
+        std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| std::fs::read_to_string(entry.unwrap().path()).ok())
+            .all(|content| !content.contains(plex_mock::AUTH_TOKEN)),
+        "The token was stored in the cache"
+    );
+}
+
+#[tokio::test]
+async fn cache_control_is_respected() {
+    for (cache_control, expected_requests) in &[("max-age=60", 1), ("no-store", 2)] {
+        let transport = CacheControlTransport {
+            cache_control,
+            body: b"cached",
+            requests: Arc::default(),
+        };
+        let client = ClientBuilder::default()
+            .client_identifier("cached-client")
+            .transport(transport.clone())
+            .response_cache(InMemoryCache::default())
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let response = client
+                .request(Method::GET, "http://plex.local/")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!("cached", response.text());
+        }
+        assert_eq!(
+            *expected_requests,
+            *transport.requests.lock().unwrap(),
+            "Unexpected number of requests for Cache-Control: {}",
+            cache_control
+        );
+    }
+}
+
+async fn assert_binary_response_cached<C: ResponseCache + 'static>(cache: C) {
+    const BODY: &[u8] = &[0x89, b'P', b'N', b'G', 0xff, 0x00, 0xfe];
+
+    let transport = CacheControlTransport {
+        cache_control: "max-age=60",
+        body: BODY,
+        requests: Arc::default(),
+    };
+    let client = ClientBuilder::default()
+        .client_identifier("cached-client")
+        .transport(transport.clone())
+        .response_cache(cache)
+        .build()
+        .unwrap();
+
+    for _ in 0..2 {
+        let response = client
+            .request(Method::GET, "http://plex.local/photo.png")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(BODY, response.bytes());
+    }
+    assert_eq!(1, *transport.requests.lock().unwrap());
+}
+
+#[tokio::test]
+async fn binary_responses_are_cached_intact() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_binary_response_cached(InMemoryCache::default()).await;
+    assert_binary_response_cached(DiskCache::new(dir.path()).unwrap()).await;
+}
+
+#[tokio::test]
+async fn unexpected_not_modified_is_requested_again() {
+    let transport = NotModifiedTransport::default();
+    let client = ClientBuilder::default()
+        .client_identifier("cached-client")
+        .transport(transport.clone())
+        .response_cache(InMemoryCache::default())
+        .build()
+        .unwrap();
+
+    let response = client
+        .request(Method::GET, "http://plex.local/")
+        .header(IF_NONE_MATCH, "\"stale\"")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("fresh", response.text());
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(2, requests.len());
+    assert!(!requests[1].get_headers().contains_key(IF_NONE_MATCH));
+}
@@ -0,0 +1,134 @@
+use crate::{
+    Cassette, ClientBuilder, MyPlexAccount, PlexApiError, PlexClient, RecordingTransport,
+    ReplayTransport, ReqwestTransport, Server,
+};
+use plex_mock::MockServer;
+
+const REPLAY_SERVER_URL: &str = "http://plex.replay:32400/";
+
+async fn sections_titles(client: &PlexClient, url: &str) -> Vec<String> {
+    let srv = Server::connect_with_client(client, url, plex_mock::AUTH_TOKEN)
+        .await
+        .unwrap();
+    let library = srv.get_sections().await.unwrap();
+    library
+        .get_directory()
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|d| d.title.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn recorded_cassette_is_scrubbed_and_replayed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cassette.json");
+
+    let recorded_titles = {
+        let mock = MockServer::start().await.unwrap();
+        let client = ClientBuilder::default()
+            .client_identifier("cassette-client")
+            .my_plex_url(&mock.url())
+            .transport(RecordingTransport::new(ReqwestTransport::default(), &path))
+            .build()
+            .unwrap();
+
+        let account =
+            MyPlexAccount::login_with_client(&client, plex_mock::USERNAME, plex_mock::PASSWORD)
+                .await
+                .unwrap();
+        account.get_resources().await.unwrap();
+        sections_titles(&client, &mock.url()).await
+    };
+
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        !recorded.contains(plex_mock::AUTH_TOKEN),
+        "Token was recorded"
+    );
+    assert!(
+        !recorded.contains(plex_mock::PASSWORD),
+        "Password was recorded"
+    );
+    assert!(recorded.contains(crate::http::REDACTED));
+    assert_eq!(4, Cassette::load(&path).unwrap().len());
+
+    let client = ClientBuilder::default()
+        .client_identifier("cassette-client")
+        .my_plex_url("http://plex.tv.replay/")
+        .transport(ReplayTransport::from_file(&path).unwrap())
+        .build()
+        .unwrap();
+
+    let account =
+        MyPlexAccount::login_with_client(&client, plex_mock::USERNAME, plex_mock::PASSWORD).await;
+    assert!(
+        account.is_ok(),
+        "Unable to replay login: {:?}",
+        account.err()
+    );
+    let resources = account.unwrap().get_resources().await;
+    assert!(
+        resources.is_ok(),
+        "Unable to replay resources: {:?}",
+        resources.err()
+    );
+    assert_eq!(
+        recorded_titles,
+        sections_titles(&client, REPLAY_SERVER_URL).await
+    );
+}
+
+#[tokio::test]
+async fn missing_interaction_is_reported() {
+    let client = ClientBuilder::default()
+        .client_identifier("cassette-client")
+        .transport(ReplayTransport::from(Cassette::default()))
+        .build()
+        .unwrap();
+
+    let srv = Server::connect_with_client(&client, REPLAY_SERVER_URL, "").await;
+    match srv {
+        Err(PlexApiError::CassetteInteractionNotFound { method, .. }) => assert_eq!("GET", method),
+        r => panic!("Unexpected result: {:?}", r),
+    }
+}
+
+#[tokio::test]
+async fn recorded_identity_and_attributes_are_scrubbed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cassette.json");
+    let body = "<MediaContainer>\n<Device\nauthToken=\"secret-1\" name=\"a\" />\n<Device\tauthToken=\"secret-2\" myauthToken=\"kept\" />\n</MediaContainer>";
+    let transport = crate::tests::transport::FakeTransport::default().respond(
+        "/devices.xml",
+        reqwest::StatusCode::OK,
+        body,
+    );
+    let client = ClientBuilder::default()
+        .client_identifier("cassette-client")
+        .device_name("my-laptop")
+        .platform_version("1.2.3-secret")
+        .transport(RecordingTransport::new(transport, &path))
+        .build()
+        .unwrap();
+
+    client
+        .request(reqwest::Method::GET, "http://plex.local/devices.xml")
+        .send()
+        .await
+        .unwrap();
+
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    assert!(!recorded.contains("secret-1"), "Attribute was recorded");
+    assert!(!recorded.contains("secret-2"), "Attribute was recorded");
+    assert!(
+        recorded.contains("kept"),
+        "Unrelated attribute was scrubbed"
+    );
+    assert!(!recorded.contains("my-laptop"), "Device name was recorded");
+    assert!(
+        !recorded.contains("1.2.3-secret"),
+        "Platform version was recorded"
+    );
+}
@@ -0,0 +1,172 @@
+use super::transport::FakeTransport;
+use crate::{
+    ClientBuilder, Format, MyPlexAccount, Pagination, PlexApiError, PlexClient, Response, Server,
+};
+use futures::TryStreamExt;
+use plex_mock::MockServer;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::StatusCode;
+
+fn client_for(mock: &MockServer, format: Format) -> PlexClient {
+    ClientBuilder::default()
+        .client_identifier("plex-mock-format-client")
+        .my_plex_url(&mock.url())
+        .format(format)
+        .build()
+        .unwrap()
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Container {
+    size: u32,
+    #[serde(rename = "Item")]
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Item {
+    key: String,
+}
+
+fn response(content_type: Option<&'static str>, body: &str) -> Response {
+    let mut headers = HeaderMap::new();
+    if let Some(content_type) = content_type {
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    }
+    Response::new(StatusCode::OK, headers, body.into())
+}
+
+#[test]
+fn both_formats_are_decoded_into_the_same_model() {
+    let json = r#"{"MediaContainer":{"size":1,"Item":[{"key":"/library/metadata/1"}]}}"#;
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<MediaContainer size="1"><Item key="/library/metadata/1"/></MediaContainer>"#;
+
+    let from_json = response(Some("application/json"), json);
+    assert_eq!(Some(Format::Json), from_json.format());
+    let from_xml = response(Some("text/xml;charset=utf-8"), xml);
+    assert_eq!(Some(Format::Xml), from_xml.format());
+
+    let expected = Container {
+        size: 1,
+        items: vec![Item {
+            key: "/library/metadata/1".to_owned(),
+        }],
+    };
+    assert_eq!(expected, from_json.decode::<Container>().unwrap());
+    assert_eq!(expected, from_xml.decode::<Container>().unwrap());
+
+    // Content-Type isn't always reported, the body is used then
+    assert_eq!(Some(Format::Xml), response(None, xml).format());
+    assert_eq!(
+        expected,
+        response(None, json).decode::<Container>().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn unsuccessful_response_is_decoded_into_status_error() {
+    let transport = FakeTransport::default().respond(
+        "/api/v2/user",
+        StatusCode::UNAUTHORIZED,
+        r#"<?xml version="1.0" encoding="UTF-8"?><errors><error code="1001" message="User could not be authenticated"/></errors>"#,
+    );
+    let client = ClientBuilder::default()
+        .client_identifier("format-client")
+        .transport(transport)
+        .build()
+        .unwrap();
+
+    let err = MyPlexAccount::by_token_with_client(&client, "token")
+        .await
+        .expect_err("The token should be rejected");
+    assert!(err.is_auth_error(), "Unexpected error: {:?}", err);
+    let errors = err.get_status_error().unwrap().get_body().get_errors();
+    assert_eq!(Some(1001), errors[0].get_code());
+}
+
+#[tokio::test]
+async fn accept_header_follows_client_preference() {
+    let transport = FakeTransport::default()
+        .respond("/", StatusCode::OK, super::transport::SERVER_XML)
+        .respond("/:/prefs", StatusCode::NOT_FOUND, "");
+    let client = ClientBuilder::default()
+        .client_identifier("format-client")
+        .transport(transport.clone())
+        .format(Format::Xml)
+        .build()
+        .unwrap();
+    assert_eq!(Format::Xml, client.get_format());
+
+    let srv = Server::connect_with_client(&client, "http://plex.local:32400/", "token")
+        .await
+        .unwrap();
+    let err = srv
+        .get_settings()
+        .await
+        .expect_err("Settings aren't served");
+    assert!(matches!(err, PlexApiError::NotFound(_)));
+
+    let requests = transport.requests();
+    assert_eq!(
+        "application/xml",
+        requests[1].get_headers().get("accept").unwrap()
+    );
+}
+
+#[tokio::test]
+async fn mock_server_library_in_both_formats() {
+    let mock = MockServer::start().await.unwrap();
+
+    let mut sizes = vec![];
+    for format in [Format::Json, Format::Xml] {
+        let srv = Server::connect_with_client(
+            &client_for(&mock, format),
+            &mock.url(),
+            plex_mock::AUTH_TOKEN,
+        )
+        .await
+        .unwrap();
+
+        let sections = srv.get_sections().await;
+        assert!(
+            sections.is_ok(),
+            "Unable to get sections as {:?}: {:?}",
+            format,
+            sections.err()
+        );
+        let sections = sections.unwrap();
+        let section = &sections.get_directory().as_ref().unwrap()[0];
+
+        let items: Vec<_> = sections
+            .stream_section_items(section, Pagination::default())
+            .try_collect()
+            .await
+            .unwrap_or_else(|e| panic!("Unable to stream items as {:?}: {:?}", format, e));
+        sizes.push(items.len());
+
+        let settings = srv.get_settings().await;
+        assert!(
+            settings.is_ok(),
+            "Unable to get settings as {:?}: {:?}",
+            format,
+            settings.err()
+        );
+        assert!(settings.unwrap().get("FriendlyName").is_ok());
+    }
+
+    assert_eq!(vec![plex_mock::SECTION_SIZE as usize; 2], sizes);
+}
+
+#[tokio::test]
+async fn my_plex_endpoints_request_supported_format() {
+    let mock = MockServer::start().await.unwrap();
+    let account =
+        MyPlexAccount::by_token_with_client(&client_for(&mock, Format::Xml), plex_mock::AUTH_TOKEN)
+            .await
+            .expect("Unable to get the account with XML preferred");
+
+    assert!(account.get_privacy().await.is_ok());
+    assert!(account.get_webhooks().await.is_ok());
+    assert!(!account.get_resources().await.unwrap().is_empty());
+}
@@ -1,8 +1,9 @@
-use crate::http::base_headers;
+use crate::ClientBuilder;
 
 #[test]
 fn base_headers_contains_required_headers() {
-    let headers = base_headers().unwrap();
+    let client = ClientBuilder::default().build().unwrap();
+    let headers = client.get_headers();
     assert!(headers.contains_key("x-plex-provides"));
     assert!(headers.contains_key("x-plex-product"));
     assert!(headers.contains_key("x-plex-version"));
@@ -14,66 +15,83 @@ fn base_headers_contains_required_headers() {
     assert!(headers.contains_key("x-plex-device-name"));
 }
 
-#[cfg(not(any(
-    feature = "test_connect_authenticated",
-    feature = "test_connect_anonymous"
-)))]
 #[test]
 fn base_headers_use_provided_values() {
-    use crate::{
-        X_PLEX_CLIENT_IDENTIFIER, X_PLEX_DEVICE, X_PLEX_DEVICE_NAME, X_PLEX_PLATFORM,
-        X_PLEX_PLATFORM_VERSION, X_PLEX_PRODUCT, X_PLEX_PROVIDES, X_PLEX_SYNC_VERSION,
-        X_PLEX_VERSION,
-    };
+    let client = ClientBuilder::default()
+        .provides("plex_provides")
+        .platform("plex_platform")
+        .platform_version("plex_platform_version")
+        .product("plex_product")
+        .version("plex_version")
+        .device("plex_device")
+        .device_name("plex_device_name")
+        .sync_version("plex_sync_version")
+        .client_identifier("plex_client_identifier")
+        .build()
+        .unwrap();
+    let headers = client.get_headers();
 
-    {
-        let mut provides = X_PLEX_PROVIDES.write().unwrap();
-        *provides = "plex_provides";
-        let mut platform = X_PLEX_PLATFORM.write().unwrap();
-        *platform = "plex_platform";
-        let mut platform_version = X_PLEX_PLATFORM_VERSION.write().unwrap();
-        *platform_version = "plex_platform_version";
-        let mut product = X_PLEX_PRODUCT.write().unwrap();
-        *product = "plex_product";
-        let mut version = X_PLEX_VERSION.write().unwrap();
-        *version = "plex_version";
-        let mut device = X_PLEX_DEVICE.write().unwrap();
-        *device = "plex_device";
-        let mut device_name = X_PLEX_DEVICE_NAME.write().unwrap();
-        *device_name = "plex_device_name";
-        let mut client_identifier = X_PLEX_CLIENT_IDENTIFIER.write().unwrap();
-        *client_identifier = "plex_client_identifier".to_owned();
-    }
-
-    assert!(crate::clear_headers_cache().is_ok()); // ensure that there are no cached headers
-    let headers = base_headers().unwrap();
-
-    let provides = X_PLEX_PROVIDES.read().unwrap();
-    let platform = X_PLEX_PLATFORM.read().unwrap();
-    let platform_version = X_PLEX_PLATFORM_VERSION.read().unwrap();
-    let product = X_PLEX_PRODUCT.read().unwrap();
-    let version = X_PLEX_VERSION.read().unwrap();
-    let device = X_PLEX_DEVICE.read().unwrap();
-    let device_name = X_PLEX_DEVICE_NAME.read().unwrap();
-    let client_identifier = X_PLEX_CLIENT_IDENTIFIER.read().unwrap();
-    let plex_sync_version = X_PLEX_SYNC_VERSION.read().unwrap();
-
-    assert_eq!(*provides, headers.get("x-plex-provides").unwrap());
-    assert_eq!(*product, headers.get("x-plex-product").unwrap());
-    assert_eq!(*version, headers.get("x-plex-version").unwrap());
+    assert_eq!("plex_provides", headers.get("x-plex-provides").unwrap());
+    assert_eq!("plex_product", headers.get("x-plex-product").unwrap());
+    assert_eq!("plex_version", headers.get("x-plex-version").unwrap());
     assert_eq!(
-        *plex_sync_version,
+        "plex_sync_version",
         headers.get("x-plex-sync-version").unwrap()
     );
-    assert_eq!(*platform, headers.get("x-plex-platform").unwrap());
+    assert_eq!("plex_platform", headers.get("x-plex-platform").unwrap());
     assert_eq!(
-        *platform_version,
+        "plex_platform_version",
         headers.get("x-plex-platform-version").unwrap()
     );
     assert_eq!(
-        client_identifier.as_str(),
+        "plex_client_identifier",
         headers.get("x-plex-client-identifier").unwrap()
     );
-    assert_eq!(*device, headers.get("x-plex-device").unwrap());
-    assert_eq!(*device_name, headers.get("x-plex-device-name").unwrap());
+    assert_eq!("plex_client_identifier", client.get_client_identifier());
+    assert_eq!("plex_device", headers.get("x-plex-device").unwrap());
+    assert_eq!(
+        "plex_device_name",
+        headers.get("x-plex-device-name").unwrap()
+    );
+}
+
+#[test]
+fn clients_have_independent_headers() {
+    let first = ClientBuilder::default()
+        .product("first_product")
+        .client_identifier("first_identifier")
+        .build()
+        .unwrap();
+    let second = ClientBuilder::default()
+        .product("second_product")
+        .client_identifier("second_identifier")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        "first_product",
+        first.get_headers().get("x-plex-product").unwrap()
+    );
+    assert_eq!(
+        "second_product",
+        second.get_headers().get("x-plex-product").unwrap()
+    );
+    assert_eq!("first_identifier", first.get_client_identifier());
+    assert_eq!("second_identifier", second.get_client_identifier());
+}
+
+#[test]
+fn default_client_identifier_is_shared() {
+    let first = ClientBuilder::default().build().unwrap();
+    let second = ClientBuilder::default().build().unwrap();
+
+    assert!(!first.get_client_identifier().is_empty());
+    assert_eq!(
+        first.get_client_identifier(),
+        second.get_client_identifier()
+    );
+    assert_eq!(
+        first.get_client_identifier(),
+        first.get_headers().get("x-plex-client-identifier").unwrap()
+    );
 }
@@ -56,4 +56,14 @@ fn decode_devices() {
 
     let mc = from_str::<DevicesMediaContainer>(s);
     assert!(mc.is_ok(), "Unable to deserialize devices: {:?}", mc.err());
+
+    let mc = mc.unwrap();
+    let server = mc
+        .get_devices()
+        .iter()
+        .find(|d| d.get_name() == "LED-Kremen-286")
+        .unwrap();
+    assert_eq!(&vec!["server".to_owned()], server.get_provides());
+    let connections = server.get_connections().as_ref().unwrap();
+    assert_eq!("http://127.0.0.1:24507/", connections[1].get_uri().as_str());
 }
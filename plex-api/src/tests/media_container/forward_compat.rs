@@ -0,0 +1,95 @@
+use crate::{LibraryMediaContainer, MediaStream, MediaStreamType, MediaType, UsersMediaContainer};
+use serde_json::{json, Value};
+
+const RECENTLY_ADDED_JSON: &str =
+    include_str!("../../../../plex-mock/fixtures/library_recently_added.json");
+
+fn container_with_item(update: impl FnOnce(&mut Value)) -> Value {
+    let mut container: Value = serde_json::from_str(RECENTLY_ADDED_JSON).unwrap();
+    let mut item = container["MediaContainer"]["Metadata"][0].clone();
+    update(&mut item);
+    container["MediaContainer"]["Metadata"] = json!([item]);
+    container["MediaContainer"]["newContainerAttribute"] = json!("container");
+    container["MediaContainer"].take()
+}
+
+#[test]
+fn unknown_attributes_are_collected() {
+    let container = container_with_item(|item| {
+        item["newItemAttribute"] = json!({ "nested": [1, 2] });
+    });
+
+    let mc = serde_json::from_value::<LibraryMediaContainer>(container).unwrap();
+    assert_eq!(
+        Some(&json!("container")),
+        mc.get_extra().get("newContainerAttribute")
+    );
+    let item = &mc.get_metadata().as_ref().unwrap()[0];
+    assert_eq!(
+        Some(&json!({ "nested": [1, 2] })),
+        item.get_extra().get("newItemAttribute")
+    );
+    assert!(!item.get_extra().contains_key("title"));
+}
+
+#[test]
+fn unknown_enum_values_are_kept() {
+    for (value, expected) in [
+        ("clip", MediaType::Clip),
+        ("collection", MediaType::Collection),
+        ("playlist", MediaType::Playlist),
+        ("hologram", MediaType::Unknown("hologram".to_owned())),
+    ] {
+        let container = container_with_item(|item| item["type"] = json!(value));
+        let mc = serde_json::from_value::<LibraryMediaContainer>(container).unwrap();
+        assert_eq!(
+            &expected,
+            mc.get_metadata().as_ref().unwrap()[0].get_media_type()
+        );
+    }
+
+    let stream = |stream_type: u8| {
+        serde_json::from_value::<MediaStream>(json!({
+            "id": 1,
+            "streamType": stream_type,
+            "codec": "lrc",
+            "index": 0,
+            "displayTitle": "LRC (English)",
+            "key": "/library/streams/1",
+            "format": "lrc",
+        }))
+        .unwrap()
+    };
+    match stream(4) {
+        MediaStream::Lyrics(lyrics) => {
+            assert_eq!(Some(&json!("lrc")), lyrics.get_extra().get("format"))
+        }
+        other => panic!("Unexpected stream: {:?}", other),
+    }
+    match stream(42) {
+        MediaStream::Unknown(unknown) => {
+            assert_eq!(&MediaStreamType::Unknown(42), unknown.get_stream_type())
+        }
+        other => panic!("Unexpected stream: {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_xml_attributes_and_values_are_kept() {
+    let s = r##"
+<?xml version="1.0" encoding="UTF-8"?>
+<MediaContainer friendlyName="myPlex" identifier="com.plexapp.plugins.myplex" machineIdentifier="19A4B805E9021" totalSize="1" size="1" newContainerAttribute="1">
+    <User id="8676063" title="18A0A7BD14ECC" thumb="https://plex.tv/users/0d9d14488439289a/avatar?c=1536695183" protected="0" home="1" allowSync="0" allowCameraUpload="0" allowChannels="0" allowTuners="7" allowSubtitleAdmin="0" filterAll="" filterMovies="" filterMusic="" filterPhotos="" filterTelevision="" restricted="1" newUserAttribute="yes">
+    </User>
+</MediaContainer>
+    "##;
+
+    // allowTuners="7" isn't known either
+    let mc = quick_xml::de::from_str::<UsersMediaContainer>(s).unwrap();
+    assert!(mc.get_extra().contains_key("newContainerAttribute"));
+    let user = &mc.get_users()[0];
+    assert_eq!(
+        Some(&json!("yes")),
+        user.get_extra().get("newUserAttribute")
+    );
+}
@@ -1,4 +1,4 @@
-use crate::LibraryMediaContainer;
+use crate::{LibraryMediaContainer, MediaStream};
 use serde_json::from_str;
 
 #[test]
@@ -27,4 +27,24 @@ fn decode_library_on_deck() {
         "Unable to deserialize library on deck: {:?}",
         mc.err()
     );
+
+    let mc = mc.unwrap();
+    let item = &mc.get_metadata().as_ref().unwrap()[0];
+    assert_eq!("12285", item.get_rating_key());
+    assert_eq!("Hail to the Teeth", item.get_title());
+    assert_eq!(
+        &Some("The Simpsons".to_owned()),
+        item.get_grandparent_title()
+    );
+    let media = &item.get_media()[0];
+    assert_eq!(1080, media.get_height());
+    let part = &media.get_parts().as_ref().unwrap()[0];
+    assert_eq!(730248189, part.get_size());
+    match &part.get_streams()[0] {
+        MediaStream::Video(video) => {
+            assert_eq!("h264", video.get_codec());
+            assert_eq!(1920, video.get_width());
+        }
+        stream => panic!("Unexpected stream: {:?}", stream),
+    }
 }
@@ -1,6 +1,9 @@
 mod devices;
+#[cfg(not(feature = "test_new_attributes"))]
+mod forward_compat;
 mod library;
 mod resources;
+mod round_trip;
 mod server;
 mod settings;
 mod users;
@@ -0,0 +1,95 @@
+use crate::{
+    DevicesMediaContainer, Format, LibraryMediaContainer, Media, MediaMetadata, MediaPart,
+    MediaStream, MyPlexAccount, ServerMediaContainer, SettingsMediaContainer, UsersMediaContainer,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Loads the mock server's fixture with the placeholders filled in.
+macro_rules! fixture {
+    ($name:literal) => {
+        include_str!(concat!("../../../../plex-mock/fixtures/", $name))
+            .replace("{{base_url}}", "http://plex.mock:32400")
+            .replace("{{username}}", "mock-user")
+            .replace("{{auth_token}}", "mock-auth-token")
+            .replace("{{friendly_name}}", "plex-mock")
+            .as_bytes()
+    };
+}
+
+/// Decodes the fixture, serializes it to JSON, reads it back and checks that nothing has been
+/// lost on the way.
+fn assert_round_trip<T: DeserializeOwned + Serialize>(format: Format, body: &[u8]) -> T {
+    let decoded: T = format.decode(body).expect("Unable to decode the fixture");
+    let json = serde_json::to_value(&decoded).expect("Unable to serialize the model");
+
+    let restored: T = serde_json::from_value(json.clone())
+        .unwrap_or_else(|e| panic!("Unable to deserialize {}: {:?}", json, e));
+    assert_eq!(json, serde_json::to_value(&restored).unwrap());
+
+    restored
+}
+
+#[test]
+fn my_plex_models_round_trip() {
+    let account: MyPlexAccount = assert_round_trip(Format::Json, fixture!("account.json"));
+    assert_eq!(
+        Value::from(1000001),
+        serde_json::to_value(&account).unwrap()["id"]
+    );
+
+    let devices: DevicesMediaContainer = assert_round_trip(Format::Json, fixture!("devices.json"));
+    assert!(!devices.get_devices().is_empty());
+    assert_round_trip::<DevicesMediaContainer>(Format::Json, fixture!("resources.json"));
+
+    let users: UsersMediaContainer = assert_round_trip(Format::Json, fixture!("users.json"));
+    assert!(!users.get_users().is_empty());
+}
+
+#[test]
+fn server_models_round_trip() {
+    let server: ServerMediaContainer = assert_round_trip(Format::Json, fixture!("server.json"));
+    let json = serde_json::to_value(&server).unwrap();
+    // The version is written the way Plex reports it
+    assert_eq!(
+        server.get_version().to_string().replacen('+', ".", 1),
+        json["version"]
+    );
+
+    let settings: SettingsMediaContainer = assert_round_trip(Format::Json, fixture!("prefs.json"));
+    assert!(settings.get("FriendlyName").is_ok());
+}
+
+/// Checks that a nested model is serialized the same way on its own, as a part of the container.
+fn assert_nested_round_trip<T: DeserializeOwned + Serialize>(json: &Value) {
+    let nested: T = serde_json::from_value(json.clone())
+        .unwrap_or_else(|e| panic!("Unable to deserialize {}: {:?}", json, e));
+    assert_eq!(json, &serde_json::to_value(&nested).unwrap());
+}
+
+#[test]
+fn library_models_round_trip() {
+    assert_round_trip::<LibraryMediaContainer>(Format::Json, fixture!("library_sections.json"));
+
+    for body in [
+        fixture!("library_on_deck.json"),
+        fixture!("library_recently_added.json"),
+    ] {
+        let mc: LibraryMediaContainer = assert_round_trip(Format::Json, body);
+        let json = serde_json::to_value(&mc).unwrap();
+
+        for item in json["Metadata"].as_array().unwrap() {
+            assert_nested_round_trip::<MediaMetadata>(item);
+            for media in item["Media"].as_array().unwrap() {
+                assert_nested_round_trip::<Media>(media);
+                for part in media["Part"].as_array().unwrap() {
+                    assert_nested_round_trip::<MediaPart>(part);
+                    for stream in part["Stream"].as_array().into_iter().flatten() {
+                        assert_nested_round_trip::<MediaStream>(stream);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::media_container::UsersMediaContainer;
+use crate::media_container::{ContentFilter, UsersMediaContainer};
 use quick_xml::de::from_str;
 
 #[test]
@@ -19,4 +19,36 @@ fn decode_users() {
 
     let mc = from_str::<UsersMediaContainer>(s);
     assert!(mc.is_ok(), "Unable to deserialize users: {:?}", mc.err());
+
+    let mc = mc.unwrap();
+    let user = &mc.get_users()[0];
+    assert_eq!(
+        &["G".to_owned()],
+        user.get_filter_movies().get_allowed_content_ratings()
+    );
+    assert!(user.get_filter_all().is_empty());
+}
+
+#[test]
+fn content_filter_round_trip() {
+    let raw = "contentRating=G%2CPG-13|contentRating!=R|label=Kids|label!=Horror%2CScary+Movies|year=2000";
+    let filter = ContentFilter::from(raw);
+    assert_eq!(
+        &["G".to_owned(), "PG-13".to_owned()],
+        filter.get_allowed_content_ratings()
+    );
+    assert_eq!(&["R".to_owned()], filter.get_excluded_content_ratings());
+    assert_eq!(&["Kids".to_owned()], filter.get_included_labels());
+    assert_eq!(
+        &["Horror".to_owned(), "Scary Movies".to_owned()],
+        filter.get_excluded_labels()
+    );
+    assert_eq!(
+        &[("year".to_owned(), "2000".to_owned())],
+        filter.get_extra()
+    );
+    assert_eq!(raw, filter.to_string());
+
+    assert!(ContentFilter::from("").is_empty());
+    assert_eq!("", ContentFilter::default().to_string());
 }
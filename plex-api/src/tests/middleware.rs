@@ -0,0 +1,146 @@
+use super::transport::{FakeTransport, SERVER_XML};
+use crate::{
+    ClientBuilder, HttpTransport, Middleware, PlexApiError, Request, Response, Result, Server,
+};
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Middleware, which adds a header and remembers every call.
+#[derive(Debug, Clone)]
+struct Recorder {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Recorder {
+    fn on_request(&self, request: &mut Request) {
+        request
+            .headers_mut()
+            .insert("X-Plex-Language", "de".parse().unwrap());
+        self.calls.lock().unwrap().push(format!(
+            "{} request {}",
+            self.name,
+            request.get_url().path()
+        ));
+    }
+
+    fn on_response(&self, _request: &Request, response: &mut Response, _elapsed: Duration) {
+        response
+            .headers_mut()
+            .insert("X-Seen-By", self.name.parse().unwrap());
+        self.calls.lock().unwrap().push(format!(
+            "{} response {} {}",
+            self.name,
+            response.status().as_u16(),
+            response.bytes().len()
+        ));
+    }
+
+    fn on_error(&self, _request: &Request, error: &PlexApiError, _elapsed: Duration) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} error {}", self.name, error));
+    }
+}
+
+#[derive(Debug)]
+struct FailingTransport;
+
+#[async_trait]
+impl HttpTransport for FailingTransport {
+    async fn execute(&self, _request: Request) -> Result<Response> {
+        Err(PlexApiError::ClientIsNotSet)
+    }
+}
+
+#[tokio::test]
+async fn middlewares_wrap_every_request() {
+    let transport = FakeTransport::default().respond("/", StatusCode::OK, SERVER_XML);
+    let calls = Arc::new(Mutex::new(vec![]));
+    let client = ClientBuilder::default()
+        .client_identifier("middleware-client")
+        .transport(transport.clone())
+        .middleware(Recorder {
+            name: "outer",
+            calls: calls.clone(),
+        })
+        .middleware(Recorder {
+            name: "inner",
+            calls: calls.clone(),
+        })
+        .build()
+        .unwrap();
+
+    Server::connect_with_client(&client, "http://plex.local:32400/", "token")
+        .await
+        .unwrap();
+
+    let body_len = SERVER_XML.len();
+    assert_eq!(
+        vec![
+            "outer request /".to_owned(),
+            "inner request /".to_owned(),
+            format!("inner response 200 {}", body_len),
+            format!("outer response 200 {}", body_len),
+        ],
+        *calls.lock().unwrap()
+    );
+    assert_eq!(
+        "de",
+        transport.requests()[0]
+            .get_headers()
+            .get("X-Plex-Language")
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn middlewares_can_change_responses() {
+    let calls = Arc::new(Mutex::new(vec![]));
+    let client = ClientBuilder::default()
+        .client_identifier("middleware-client")
+        .transport(FakeTransport::default().respond("/", StatusCode::OK, ""))
+        .build()
+        .unwrap()
+        .with_middleware(Recorder {
+            name: "added",
+            calls,
+        });
+
+    let response = client
+        .request(Method::GET, "http://plex.local/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!("added", response.headers().get("X-Seen-By").unwrap());
+}
+
+#[tokio::test]
+async fn middlewares_observe_errors() {
+    let calls = Arc::new(Mutex::new(vec![]));
+    let client = ClientBuilder::default()
+        .client_identifier("middleware-client")
+        .transport(FailingTransport)
+        .middleware(Recorder {
+            name: "recorder",
+            calls: calls.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let result = client
+        .request(Method::GET, "http://plex.local/")
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        vec![
+            "recorder request /".to_owned(),
+            format!("recorder error {}", PlexApiError::ClientIsNotSet),
+        ],
+        *calls.lock().unwrap()
+    );
+}
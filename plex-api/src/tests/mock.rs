@@ -0,0 +1,809 @@
+use crate::{
+    AllowTuners, AutoSelectSubtitleMode, ClientBuilder, ContentFilter, Feature, Format,
+    HasMyPlexToken, MyPlexAccount, Pagination, Pin, PlexApiError, PlexClient, RestrictionProfile,
+    Server, Session, SettingValue, SharingSettings, SubscriptionPlan, SubscriptionSummary,
+};
+use futures::TryStreamExt;
+use plex_mock::MockServer;
+use std::time::Duration;
+
+fn client_for(mock: &MockServer) -> PlexClient {
+    ClientBuilder::default()
+        .client_identifier("plex-mock-client")
+        .my_plex_url(&mock.url())
+        .build()
+        .unwrap()
+}
+
+async fn server_for(mock: &MockServer) -> Server {
+    Server::connect_with_client(&client_for(mock), &mock.url(), plex_mock::AUTH_TOKEN)
+        .await
+        .expect("Unable to connect to the mock")
+}
+
+async fn account_for(mock: &MockServer) -> MyPlexAccount {
+    MyPlexAccount::by_token_with_client(&client_for(mock), plex_mock::AUTH_TOKEN)
+        .await
+        .expect("Unable to get the mock's account")
+}
+
+#[tokio::test]
+async fn mock_server_connect_and_refresh() {
+    let mock = MockServer::start().await.unwrap();
+    let mut srv = server_for(&mock).await;
+
+    let refreshed = srv.refresh().await;
+    assert!(
+        refreshed.is_ok(),
+        "Unable to refresh: {:?}",
+        refreshed.err()
+    );
+}
+
+#[tokio::test]
+async fn mock_server_library() {
+    let mock = MockServer::start().await.unwrap();
+    let srv = server_for(&mock).await;
+
+    let sections = srv.get_sections().await;
+    assert!(
+        sections.is_ok(),
+        "Unable to get sections: {:?}",
+        sections.err()
+    );
+
+    let recently_added = srv.get_recently_added().await;
+    assert!(
+        recently_added.is_ok(),
+        "Unable to get recently added: {:?}",
+        recently_added.err()
+    );
+
+    let on_deck = srv.get_on_deck().await;
+    assert!(
+        on_deck.is_ok(),
+        "Unable to get on deck: {:?}",
+        on_deck.err()
+    );
+}
+
+#[tokio::test]
+async fn mock_server_library_outlives_the_server() {
+    let mock = MockServer::start().await.unwrap();
+    let srv = server_for(&mock).await;
+
+    let clone = srv.clone();
+    let sections = tokio::spawn(async move { clone.get_sections().await })
+        .await
+        .unwrap()
+        .expect("Unable to get sections");
+    drop(srv);
+
+    let on_deck = tokio::spawn(async move { sections.get_server().get_on_deck().await })
+        .await
+        .unwrap();
+    assert!(
+        on_deck.is_ok(),
+        "Unable to get on deck: {:?}",
+        on_deck.err()
+    );
+}
+
+#[tokio::test]
+async fn mock_server_section_items_are_streamed_by_pages() {
+    let mock = MockServer::start().await.unwrap();
+    let srv = server_for(&mock).await;
+    let library = srv.get_sections().await.unwrap();
+    let section = &library.get_directory().as_ref().unwrap()[0];
+
+    for pagination in &[
+        Pagination::default(),
+        Pagination::default().page_size(30).prefetch(3),
+        Pagination::default().page_size(1000),
+    ] {
+        let items: Vec<_> = library
+            .stream_section_items(section, *pagination)
+            .try_collect()
+            .await
+            .expect("Unable to stream section items");
+
+        let keys: Vec<_> = items
+            .iter()
+            .map(|i| i.get_rating_key().to_owned())
+            .collect();
+        let expected: Vec<_> = (0..plex_mock::SECTION_SIZE)
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(expected, keys, "Unexpected items with {:?}", pagination);
+    }
+}
+
+#[tokio::test]
+async fn mock_server_settings_are_updated() {
+    let mock = MockServer::start().await.unwrap();
+    let srv = server_for(&mock).await;
+
+    let mut settings = srv.get_settings().await.unwrap();
+    settings
+        .set("FriendlyName", SettingValue::Text("renamed".to_owned()))
+        .unwrap();
+    let updated = srv.update_settings(&settings).await;
+    assert!(
+        updated.is_ok(),
+        "Unable to update settings: {:?}",
+        updated.err()
+    );
+
+    let settings = srv.get_settings().await.unwrap();
+    match settings.get("FriendlyName").unwrap().get_value() {
+        SettingValue::Text(name) => assert_eq!("renamed", name),
+        v => panic!("Unexpected setting value: {:?}", v),
+    }
+}
+
+#[tokio::test]
+async fn mock_server_claim_and_unclaim() {
+    let mock = MockServer::start().await.unwrap();
+    let mut srv = server_for(&mock).await;
+    let account = account_for(&mock).await;
+
+    let claim_token = account.get_claim_token().await.unwrap();
+    assert_eq!(plex_mock::CLAIM_TOKEN, claim_token);
+
+    let claimed = srv.claim(&claim_token).await;
+    assert!(claimed.is_ok(), "Unable to claim: {:?}", claimed.err());
+    assert!(mock.is_claimed());
+
+    let unclaimed = srv.unclaim().await;
+    assert!(
+        unclaimed.is_ok(),
+        "Unable to unclaim: {:?}",
+        unclaimed.err()
+    );
+    assert!(!mock.is_claimed());
+}
+
+#[tokio::test]
+async fn mock_server_rejected_claim_is_a_status_error() {
+    let mock = MockServer::start().await.unwrap();
+    let mut srv = server_for(&mock).await;
+
+    let err = srv
+        .claim("claim-invalid-token")
+        .await
+        .expect_err("The claim should fail");
+    match err.get_status_error() {
+        Some(e) => assert_eq!(reqwest::StatusCode::BAD_REQUEST, e.get_status()),
+        None => panic!("Unexpected error: {:?}", err),
+    }
+    assert!(!mock.is_claimed());
+}
+
+#[tokio::test]
+async fn mock_my_plex_login() {
+    let mock = MockServer::start().await.unwrap();
+    let client = client_for(&mock);
+
+    let account =
+        MyPlexAccount::login_with_client(&client, plex_mock::USERNAME, plex_mock::PASSWORD).await;
+    assert!(account.is_ok(), "Unable to login: {:?}", account.err());
+    assert_eq!(plex_mock::USERNAME, account.unwrap().get_username());
+
+    let account = MyPlexAccount::login_with_client(&client, plex_mock::USERNAME, "wrong").await;
+    assert!(account.is_err(), "Logged in with a wrong password");
+
+    let account = MyPlexAccount::by_token_with_client(&client, "wrong").await;
+    assert!(account.is_err(), "Logged in with a wrong token");
+}
+
+#[tokio::test]
+async fn mock_my_plex_login_with_otp() {
+    let mock = MockServer::start().await.unwrap();
+    let client = client_for(&mock);
+
+    let err =
+        MyPlexAccount::login_with_client(&client, plex_mock::OTP_USERNAME, plex_mock::PASSWORD)
+            .await
+            .expect_err("Logged in without a verification code");
+    assert!(
+        matches!(err, PlexApiError::OtpRequired(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+    assert!(err.is_auth_error());
+
+    let account = MyPlexAccount::login_with_otp_with_client(
+        &client,
+        plex_mock::OTP_USERNAME,
+        plex_mock::PASSWORD,
+        plex_mock::VERIFICATION_CODE,
+    )
+    .await;
+    assert!(account.is_ok(), "Unable to login: {:?}", account.err());
+
+    let err = MyPlexAccount::login_with_otp_with_client(
+        &client,
+        plex_mock::OTP_USERNAME,
+        plex_mock::PASSWORD,
+        "000000",
+    )
+    .await
+    .expect_err("Logged in with a wrong verification code");
+    assert!(
+        matches!(err, PlexApiError::Unauthorized(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn mock_my_plex_lists() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let devices = account.get_devices().await;
+    assert!(
+        devices.is_ok(),
+        "Unable to get devices: {:?}",
+        devices.err()
+    );
+
+    let users = account.get_users().await;
+    assert!(users.is_ok(), "Unable to get users: {:?}", users.err());
+}
+
+#[tokio::test]
+async fn mock_dual_format_endpoints_follow_the_client_format() {
+    let mock = MockServer::start().await.unwrap();
+    let client = ClientBuilder::default()
+        .client_identifier("plex-mock-client")
+        .my_plex_url(&mock.url())
+        .format(Format::Xml)
+        .build()
+        .unwrap();
+
+    let srv = Server::connect_with_client(&client, &mock.url(), plex_mock::AUTH_TOKEN).await;
+    assert!(srv.is_ok(), "Unable to connect to server: {:?}", srv.err());
+
+    let account = MyPlexAccount::by_token_with_client(&client, plex_mock::AUTH_TOKEN)
+        .await
+        .unwrap();
+    let devices = account.get_devices().await;
+    assert!(
+        devices.is_ok(),
+        "Unable to get devices: {:?}",
+        devices.err()
+    );
+    let resources = account.get_resources().await;
+    assert!(
+        resources.is_ok(),
+        "Unable to get resources: {:?}",
+        resources.err()
+    );
+    let users = account.get_users().await;
+    assert!(users.is_ok(), "Unable to get users: {:?}", users.err());
+}
+
+#[tokio::test]
+async fn mock_my_plex_resource_connects_to_server() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let resources = account.get_resources().await.unwrap();
+    let device = resources
+        .iter()
+        .find(|d| d.get_name() == "plex-mock")
+        .expect("Mocked server wasn't found in resources");
+
+    let srv = device.connect_to_server().await;
+    assert!(srv.is_ok(), "Unable to connect to server: {:?}", srv.err());
+}
+
+#[tokio::test]
+async fn mock_my_plex_privacy_and_webhooks() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let privacy = account.get_privacy().await;
+    assert!(
+        privacy.is_ok(),
+        "Unable to get privacy: {:?}",
+        privacy.err()
+    );
+    let updated = account.set_privacy(true, false).await;
+    assert!(
+        updated.is_ok(),
+        "Unable to set privacy: {:?}",
+        updated.err()
+    );
+
+    account
+        .add_webhook("http://example.com/hook")
+        .await
+        .unwrap();
+    assert_eq!(
+        vec!["http://example.com/hook".to_owned()],
+        account.get_webhooks().await.unwrap()
+    );
+
+    account
+        .del_webhook("http://example.com/hook")
+        .await
+        .unwrap();
+    assert!(account.get_webhooks().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn mock_my_plex_pin_login() {
+    let mock = MockServer::start().await.unwrap();
+    let client = ClientBuilder::default()
+        .client_identifier("plex-mock-client")
+        .product("Mock App")
+        .my_plex_url(&mock.url())
+        .build()
+        .unwrap();
+
+    let mut pin = Pin::create_with_client(&client).await.unwrap();
+    assert!(!pin.is_expired());
+    assert_eq!(&None, pin.get_auth_token());
+    assert_eq!(
+        format!(
+            "https://app.plex.tv/auth#?clientID=plex-mock-client&code={}&context%5Bdevice%5D%5Bproduct%5D=Mock+App",
+            pin.get_code()
+        ),
+        pin.get_auth_url()
+    );
+
+    pin.refresh().await.unwrap();
+    assert_eq!(&None, pin.get_auth_token());
+
+    assert!(mock.link_pin(pin.get_code()));
+    let account = pin.wait_for_login(Duration::from_millis(10)).await;
+    assert!(account.is_ok(), "Unable to login: {:?}", account.err());
+    assert_eq!(plex_mock::USERNAME, account.unwrap().get_username());
+}
+
+#[tokio::test]
+async fn mock_my_plex_pin_expires() {
+    let mock = MockServer::start().await.unwrap();
+    let client = client_for(&mock);
+
+    let pin = Pin::create_with_client(&client).await.unwrap();
+
+    // PINs are bound to the client, which has created them
+    let mut other_client_pin = pin.clone();
+    other_client_pin.client = Some(
+        ClientBuilder::default()
+            .client_identifier("other-client")
+            .my_plex_url(&mock.url())
+            .build()
+            .unwrap(),
+    );
+    let err = other_client_pin
+        .refresh()
+        .await
+        .expect_err("PIN shouldn't be found");
+    assert!(
+        matches!(err, PlexApiError::NotFound(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+
+    assert!(mock.expire_pin(pin.get_code()));
+    assert!(!pin.is_expired());
+    let err = pin
+        .clone()
+        .wait_for_login(Duration::from_millis(10))
+        .await
+        .expect_err("The PIN should expire");
+    assert!(
+        matches!(&err, PlexApiError::PinExpired { code } if code == pin.get_code()),
+        "Unexpected error: {:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn mock_my_plex_session_is_saved_and_restored() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+    assert!(!account.is_expired());
+
+    let session = account.get_session().unwrap();
+    assert_eq!(plex_mock::AUTH_TOKEN, session.get_auth_token());
+    assert_eq!("plex-mock-client", session.get_client_identifier());
+    assert_eq!(mock.url(), session.get_my_plex_url());
+    assert_eq!(plex_mock::USERNAME, session.get_username());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.json");
+    std::fs::write(&path, "").unwrap();
+    session.save(&path).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+    let loaded = Session::load(&path).unwrap();
+    assert_eq!(session, loaded);
+
+    let restored = loaded.restore().await;
+    assert!(
+        restored.is_ok(),
+        "Unable to restore the session: {:?}",
+        restored.err()
+    );
+    assert_eq!(
+        "plex-mock-client",
+        restored
+            .unwrap()
+            .get_client()
+            .unwrap()
+            .get_client_identifier()
+    );
+}
+
+#[tokio::test]
+async fn mock_my_plex_sign_out_revokes_the_token() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+    let session = account.get_session().unwrap();
+
+    let signed_out = account.sign_out().await;
+    assert!(
+        signed_out.is_ok(),
+        "Unable to sign out: {:?}",
+        signed_out.err()
+    );
+
+    let err = session
+        .restore()
+        .await
+        .expect_err("The revoked token shouldn't be accepted");
+    assert!(err.is_auth_error(), "Unexpected error: {:?}", err);
+}
+
+#[tokio::test]
+async fn mock_my_plex_subscription_features() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let subscription = account.get_subscription();
+    assert!(subscription.get_active());
+    assert_eq!(&SubscriptionPlan::Lifetime, subscription.get_plan());
+    assert!(account.has_feature(Feature::Dvr));
+    assert!(account.has_feature(Feature::HardwareTranscoding));
+    assert!(!account.has_feature(Feature::Lyrics));
+    assert!(subscription
+        .get_features()
+        .contains(&Feature::Unknown("hwtranscode".to_owned())));
+
+    let plex_pass = &account.get_subscriptions()[0];
+    assert!(plex_pass.is_active());
+    assert_eq!(&SubscriptionPlan::Lifetime, plex_pass.get_mode());
+    assert!(plex_pass.get_renews_at().is_none());
+
+    let lapsed: SubscriptionSummary = serde_json::from_value(serde_json::json!({
+        "active": false,
+        "status": "Inactive",
+        "paymentService": "braintree",
+        "plan": "monthly",
+        "features": ["dvr"],
+    }))
+    .unwrap();
+    assert_eq!(&SubscriptionPlan::Monthly, lapsed.get_plan());
+    assert!(!lapsed.has_feature(Feature::Dvr));
+}
+
+#[tokio::test]
+async fn mock_my_plex_profile_is_updated() {
+    let mock = MockServer::start().await.unwrap();
+    let mut account = account_for(&mock).await;
+
+    let mut profile = account.get_profile().clone();
+    assert_eq!("en", profile.get_default_audio_language());
+    profile.set_default_audio_language("de");
+    profile.set_default_subtitle_language("fr");
+    profile.set_auto_select_subtitle(AutoSelectSubtitleMode::AlwaysEnabled);
+    profile.set_default_subtitle_forced(false);
+
+    let updated = account.update_profile(&profile).await;
+    assert!(
+        updated.is_ok(),
+        "Unable to update the profile: {:?}",
+        updated.err()
+    );
+    assert_eq!("de", account.get_profile().get_default_audio_language());
+    assert_eq!(
+        &AutoSelectSubtitleMode::AlwaysEnabled,
+        account.get_profile().get_auto_select_subtitle()
+    );
+
+    let reloaded = account_for(&mock).await;
+    let profile = reloaded.get_profile();
+    assert_eq!("de", profile.get_default_audio_language());
+    assert_eq!("fr", profile.get_default_subtitle_language());
+    assert!(!profile.get_default_subtitle_forced());
+    assert!(profile.get_auto_select_audio());
+}
+
+#[tokio::test]
+async fn mock_my_plex_home_users_switch() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let users = account.get_home_users().await.unwrap();
+    assert_eq!(2, users.len());
+    assert!(users[0].get_admin());
+    let kid = users.iter().find(|u| u.get_title() == "Kid").unwrap();
+    assert!(kid.get_protected());
+    assert_eq!(
+        &Some(RestrictionProfile::LittleKid),
+        kid.get_restriction_profile()
+    );
+
+    let err = account
+        .switch_home_user(kid, None)
+        .await
+        .expect_err("The PIN should be required");
+    assert!(err.is_auth_error(), "Unexpected error: {:?}", err);
+
+    let switched = account
+        .switch_home_user(kid, Some(plex_mock::HOME_USER_PIN))
+        .await
+        .unwrap();
+    assert_eq!(kid.get_id() as i32, switched.get_id());
+    assert_eq!("Kid", switched.get_title());
+    assert_ne!(account.get_auth_token(), switched.get_auth_token());
+
+    let admin = account.switch_home_user(&users[0], None).await.unwrap();
+    assert_eq!(account.get_auth_token(), admin.get_auth_token());
+}
+
+#[tokio::test]
+async fn mock_my_plex_managed_home_users() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let user = account
+        .create_managed_home_user("Teenager", Some(RestrictionProfile::Teen))
+        .await
+        .unwrap();
+    assert_eq!("Teenager", user.get_title());
+    assert!(user.get_restricted());
+    assert_eq!(
+        &Some(RestrictionProfile::Teen),
+        user.get_restriction_profile()
+    );
+    assert_eq!(3, account.get_home_users().await.unwrap().len());
+
+    account.remove_home_user(&user).await.unwrap();
+    assert_eq!(2, account.get_home_users().await.unwrap().len());
+
+    let err = account
+        .remove_home_user(&user)
+        .await
+        .expect_err("The user is already removed");
+    assert!(
+        matches!(err, PlexApiError::NotFound(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn mock_my_plex_share_server_with_friend() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+    let machine_identifier = plex_mock::MACHINE_IDENTIFIER;
+
+    let sections = account
+        .get_server_sections(machine_identifier)
+        .await
+        .unwrap();
+    assert_eq!(4, sections.len());
+    assert_eq!("Movies", sections[0].get_title());
+
+    let settings = SharingSettings::default()
+        .allow_sync(true)
+        .filter_movies(ContentFilter::default().allow_content_rating("G"));
+    let shared = account
+        .invite_friend(
+            machine_identifier,
+            "friend@example.com",
+            &[sections[0].get_id()],
+            &settings,
+        )
+        .await
+        .unwrap();
+    assert_eq!("friend@example.com", shared.get_email());
+    assert!(shared.get_accepted_at().is_none());
+    assert!(shared.get_allow_sync());
+    assert!(!shared.get_allow_channels());
+    assert_eq!(
+        &["G".to_owned()],
+        shared.get_filter_movies().get_allowed_content_ratings()
+    );
+    let shared_sections: Vec<_> = shared
+        .get_sections()
+        .iter()
+        .filter(|s| s.get_shared() == Some(true))
+        .map(|s| s.get_key())
+        .collect();
+    assert_eq!(vec!["1"], shared_sections);
+
+    let section_ids: Vec<_> = sections.iter().map(|s| s.get_id()).collect();
+    account
+        .update_shared_sections(machine_identifier, shared.get_id(), &section_ids)
+        .await
+        .unwrap();
+    let shared_servers = account
+        .get_shared_servers(machine_identifier)
+        .await
+        .unwrap();
+    assert_eq!(1, shared_servers.len());
+    assert!(shared_servers[0]
+        .get_sections()
+        .iter()
+        .all(|s| s.get_shared() == Some(true)));
+}
+
+#[tokio::test]
+async fn mock_my_plex_friends_are_updated_and_removed() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let users = account.get_users().await.unwrap();
+    let friend = &users[0];
+    assert!(!friend.get_allow_sync());
+
+    let settings = SharingSettings::default()
+        .allow_sync(true)
+        .filter_television(
+            ContentFilter::default()
+                .allow_content_rating("TV-G")
+                .exclude_label("Horror"),
+        );
+    account
+        .update_sharing_settings(friend, &settings)
+        .await
+        .unwrap();
+    let users = account.get_users().await.unwrap();
+    assert!(users[0].get_allow_sync());
+    assert_eq!(
+        "contentRating=TV-G|label!=Horror",
+        users[0].get_filter_television().to_string()
+    );
+
+    account.remove_friend(friend).await.unwrap();
+    let users = account.get_users().await.unwrap();
+    assert!(users.iter().all(|u| u.get_id() != friend.get_id()));
+}
+
+#[tokio::test]
+async fn mock_my_plex_user_permissions_are_updated() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let users = account.get_users().await.unwrap();
+    let user = &users[1];
+    let server = &user.get_servers().as_ref().unwrap()[0];
+    assert!(!user.get_allow_sync());
+
+    let settings = SharingSettings::from(user)
+        .allow_sync(true)
+        .allow_subtitle_admin(true)
+        .allow_tuners(AllowTuners::AllowLiveTvAndDvr);
+    let updated = account
+        .update_user_permissions(user, server, &settings)
+        .await
+        .unwrap();
+    assert_eq!(user.get_id(), updated.get_id());
+    assert!(updated.get_allow_sync());
+    assert!(updated.get_allow_subtitle_admin());
+    assert!(!updated.get_allow_camera_upload());
+    assert_eq!(&AllowTuners::AllowLiveTvAndDvr, updated.get_allow_tuners());
+    assert_eq!(settings, SharingSettings::from(&updated));
+
+    // Other users are left untouched
+    let users = account.get_users().await.unwrap();
+    assert!(!users[0].get_allow_sync());
+
+    // The server has to be shared with the user
+    let other_server = &users[0].get_servers().as_ref().unwrap()[0];
+    let err = account
+        .update_user_permissions(user, other_server, &settings)
+        .await
+        .expect_err("Updated permissions on a server, which isn't shared with the user");
+    assert!(
+        matches!(err, PlexApiError::ServerNotShared { .. }),
+        "Unexpected error: {:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn mock_my_plex_invites_are_accepted_and_rejected() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+
+    let invites = account.get_pending_invites().await.unwrap();
+    assert_eq!(1, invites.len());
+    let invite = &invites[0];
+    assert_eq!("mock-friend", invite.get_username());
+    assert!(invite.get_friend() && invite.get_server() && !invite.get_home());
+    assert_eq!(3, invite.get_servers()[0].get_num_libraries());
+
+    account.accept_invite(invite).await.unwrap();
+    assert!(account.get_pending_invites().await.unwrap().is_empty());
+
+    let err = account
+        .reject_invite(invite)
+        .await
+        .expect_err("The invite is already accepted");
+    assert!(
+        matches!(err, PlexApiError::NotFound(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn mock_my_plex_sent_invites_are_cancelled() {
+    let mock = MockServer::start().await.unwrap();
+    let account = account_for(&mock).await;
+    assert!(account.get_sent_invites().await.unwrap().is_empty());
+
+    account
+        .invite_friend(
+            plex_mock::MACHINE_IDENTIFIER,
+            "friend@example.com",
+            &[],
+            &SharingSettings::default(),
+        )
+        .await
+        .unwrap();
+    let invites = account.get_sent_invites().await.unwrap();
+    assert_eq!(1, invites.len());
+    assert_eq!("friend@example.com", invites[0].get_email());
+    assert!(invites[0].get_server());
+
+    // Sent invites can't be accepted by the sender
+    let err = account
+        .accept_invite(&invites[0])
+        .await
+        .expect_err("Accepted own invite");
+    assert!(
+        matches!(err, PlexApiError::NotFound(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+
+    account.cancel_invite(&invites[0]).await.unwrap();
+    assert!(account.get_sent_invites().await.unwrap().is_empty());
+}
+
+#[test]
+fn expired_session_is_not_restored() {
+    let session: Session = serde_json::from_value(serde_json::json!({
+        "authToken": "token",
+        "clientIdentifier": "plex-mock-client",
+        "myPlexUrl": "http://127.0.0.1:1/",
+        "uuid": "0f5e2d3c4b5a6978",
+        "username": "mock-user",
+        "title": "mock-user",
+        "email": "mock@example.com",
+        "rememberExpiresAt": 1500000000,
+    }))
+    .unwrap();
+    assert!(session.is_expired());
+
+    let err =
+        futures::executor::block_on(session.restore()).expect_err("The session should be expired");
+    assert!(
+        matches!(err, PlexApiError::SessionExpired { .. }),
+        "Unexpected error: {:?}",
+        err
+    );
+}
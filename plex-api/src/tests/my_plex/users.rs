@@ -7,8 +7,9 @@ async fn decode_users_online() {
     use std::env;
 
     let auth_token = &env::var("PLEX_API_AUTH_TOKEN").expect("Auth token not specified");
+    let client = &crate::tests::get_client();
     let (acc, _) = FutureRetry::new(
-        move || MyPlexAccount::by_token(auth_token),
+        move || MyPlexAccount::by_token_with_client(client, auth_token),
         FutureRetryHandler::new(5, "Log-in by token"),
     )
     .await
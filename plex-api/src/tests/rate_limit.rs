@@ -0,0 +1,72 @@
+use super::transport::{FakeTransport, SERVER_XML};
+use crate::{ClientBuilder, RateLimit, RateLimiter, Server};
+use reqwest::{Method, StatusCode};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn requests_over_the_limit_are_delayed() {
+    let transport = FakeTransport::default().respond("/", StatusCode::OK, "");
+    let client = ClientBuilder::default()
+        .client_identifier("rate-limited-client")
+        .transport(transport.clone())
+        .rate_limiter(
+            RateLimiter::default()
+                .host_limit("plex.tv", RateLimit::new(2, Duration::from_millis(200))),
+        )
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    for _ in 0..4 {
+        client
+            .request(Method::GET, "https://plex.tv/")
+            .send()
+            .await
+            .unwrap();
+    }
+    assert!(
+        started.elapsed() >= Duration::from_millis(190),
+        "Requests weren't delayed: {:?}",
+        started.elapsed()
+    );
+
+    let started = Instant::now();
+    for _ in 0..4 {
+        client
+            .request(Method::GET, "http://plex.local/")
+            .send()
+            .await
+            .unwrap();
+    }
+    assert!(
+        started.elapsed() < Duration::from_millis(100),
+        "Requests to another host were delayed: {:?}",
+        started.elapsed()
+    );
+    assert_eq!(8, transport.requests().len());
+}
+
+#[tokio::test]
+async fn server_rate_limiter_does_not_affect_the_client() {
+    let transport = FakeTransport::default().respond("/", StatusCode::OK, SERVER_XML);
+    let client = ClientBuilder::default()
+        .client_identifier("rate-limited-client")
+        .transport(transport)
+        .build()
+        .unwrap();
+
+    let mut srv = Server::connect_with_client(&client, "http://plex.local:32400/", "token")
+        .await
+        .unwrap();
+    let limiter = RateLimiter::default().limit(RateLimit::per_minute(10));
+    srv.set_rate_limiter(limiter.clone());
+
+    assert_eq!(
+        Some(RateLimit::per_minute(10)),
+        srv.get_client()
+            .unwrap()
+            .get_rate_limiter()
+            .and_then(|l| l.get_limit("plex.local"))
+    );
+    assert!(client.get_rate_limiter().is_none());
+}
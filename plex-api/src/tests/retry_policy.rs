@@ -0,0 +1,128 @@
+use crate::{ClientBuilder, HttpTransport, PlexClient, Request, Response, Result, RetryPolicy};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Transport, which responds with the provided statuses in order, repeating the last one.
+#[derive(Debug, Clone)]
+struct SequenceTransport {
+    statuses: Arc<Mutex<Vec<StatusCode>>>,
+    attempts: Arc<Mutex<u32>>,
+}
+
+impl SequenceTransport {
+    fn new(statuses: &[StatusCode]) -> Self {
+        SequenceTransport {
+            statuses: Arc::new(Mutex::new(statuses.iter().rev().cloned().collect())),
+            attempts: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    fn attempts(&self) -> u32 {
+        *self.attempts.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for SequenceTransport {
+    async fn execute(&self, _request: Request) -> Result<Response> {
+        *self.attempts.lock().unwrap() += 1;
+        let mut statuses = self.statuses.lock().unwrap();
+        let status = if statuses.len() > 1 {
+            statuses.pop().unwrap()
+        } else {
+            statuses[0]
+        };
+        Ok(Response::new(status, HeaderMap::new(), vec![]))
+    }
+}
+
+fn client_with(transport: &SequenceTransport, max_attempts: u32) -> PlexClient {
+    ClientBuilder::default()
+        .client_identifier("retry-client")
+        .transport(transport.clone())
+        .retry_policy(
+            RetryPolicy::default()
+                .max_attempts(max_attempts)
+                .initial_delay(Duration::from_millis(1)),
+        )
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn idempotent_request_is_retried_until_success() {
+    let transport = SequenceTransport::new(&[
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::OK,
+    ]);
+    let client = client_with(&transport, 3);
+
+    let response = client
+        .request(Method::GET, "http://plex.local/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(3, transport.attempts());
+}
+
+#[tokio::test]
+async fn last_response_is_returned_when_attempts_are_exhausted() {
+    let transport = SequenceTransport::new(&[StatusCode::BAD_GATEWAY]);
+    let client = client_with(&transport, 2);
+
+    let response = client
+        .request(Method::GET, "http://plex.local/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_GATEWAY, response.status());
+    assert_eq!(2, transport.attempts());
+}
+
+#[tokio::test]
+async fn non_idempotent_and_non_retryable_requests_are_sent_once() {
+    let transport = SequenceTransport::new(&[StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK]);
+    let client = client_with(&transport, 3);
+    client
+        .request(Method::POST, "http://plex.local/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(1, transport.attempts());
+
+    let transport = SequenceTransport::new(&[StatusCode::NOT_FOUND, StatusCode::OK]);
+    let client = client_with(&transport, 3);
+    client
+        .request(Method::GET, "http://plex.local/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(1, transport.attempts());
+}
+
+#[test]
+fn delay_grows_exponentially_and_respects_retry_after() {
+    let policy = RetryPolicy::default()
+        .initial_delay(Duration::from_secs(1))
+        .max_delay(Duration::from_secs(5))
+        .jitter(false);
+
+    assert_eq!(Duration::from_secs(1), policy.delay(1, None));
+    assert_eq!(Duration::from_secs(4), policy.delay(3, None));
+    assert_eq!(Duration::from_secs(5), policy.delay(10, None));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+    let response = Response::new(StatusCode::TOO_MANY_REQUESTS, headers, vec![]);
+    assert_eq!(Duration::from_secs(3), policy.delay(1, Some(&response)));
+
+    let jittered = RetryPolicy::default()
+        .initial_delay(Duration::from_secs(2))
+        .delay(1, None);
+    assert!(jittered >= Duration::from_secs(1) && jittered <= Duration::from_secs(2));
+}
@@ -0,0 +1,119 @@
+use super::transport::FakeTransport;
+use crate::{ClientBuilder, ErrorBody, Pagination, PlexApiError, RetryPolicy, Server};
+use futures::StreamExt;
+use plex_mock::MockServer;
+use reqwest::StatusCode;
+
+async fn connect_to_fake(status: StatusCode, body: &str) -> PlexApiError {
+    let client = ClientBuilder::default()
+        .client_identifier("status-errors-client")
+        .transport(FakeTransport::default().respond("/", status, body))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    Server::connect_with_client(&client, "http://plex.local:32400/", "token")
+        .await
+        .expect_err("The connection should fail")
+}
+
+#[tokio::test]
+async fn invalid_token_is_an_auth_error() {
+    let mock = MockServer::start().await.unwrap();
+    let client = ClientBuilder::default()
+        .client_identifier("status-errors-client")
+        .build()
+        .unwrap();
+
+    let err = Server::connect_with_client(&client, &mock.url(), "invalid-token")
+        .await
+        .expect_err("The connection should fail");
+    assert!(
+        matches!(err, PlexApiError::Unauthorized(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+    assert!(err.is_auth_error());
+    assert!(!err.is_retryable());
+
+    let details = err.get_status_error().unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, details.get_status());
+    assert_eq!(mock.url(), details.get_url());
+    assert!(details.get_body().get_raw().contains("401 Unauthorized"));
+}
+
+#[tokio::test]
+async fn missing_item_is_not_found() {
+    let mock = MockServer::start().await.unwrap();
+    let client = ClientBuilder::default()
+        .client_identifier("status-errors-client")
+        .build()
+        .unwrap();
+    let srv = Server::connect_with_client(&client, &mock.url(), plex_mock::AUTH_TOKEN)
+        .await
+        .unwrap();
+
+    let err = srv
+        .stream_metadata("library/metadata/404", Pagination::default())
+        .next()
+        .await
+        .unwrap()
+        .expect_err("The item shouldn't exist");
+    assert!(
+        matches!(err, PlexApiError::NotFound(_)),
+        "Unexpected error: {:?}",
+        err
+    );
+    assert!(err
+        .get_status_error()
+        .unwrap()
+        .get_url()
+        .ends_with("/library/metadata/404"));
+}
+
+#[tokio::test]
+async fn throttling_and_server_errors_are_retryable() {
+    let err = connect_to_fake(
+        StatusCode::TOO_MANY_REQUESTS,
+        r#"{"errors":[{"code":1004,"message":"Too many requests"}]}"#,
+    )
+    .await;
+    assert!(matches!(err, PlexApiError::TooManyRequests(_)));
+    assert!(err.is_retryable());
+    let errors = err.get_status_error().unwrap().get_body().get_errors();
+    assert_eq!(Some(1004), errors[0].get_code());
+    assert_eq!("Too many requests", errors[0].get_message());
+
+    let err = connect_to_fake(
+        StatusCode::SERVICE_UNAVAILABLE,
+        r#"<Response code="503" status="Maintenance"/>"#,
+    )
+    .await;
+    assert!(matches!(err, PlexApiError::ServerError(_)));
+    assert!(err.is_retryable());
+    assert_eq!(
+        "Maintenance",
+        err.get_status_error().unwrap().get_body().get_errors()[0].get_message()
+    );
+
+    let err = connect_to_fake(StatusCode::FORBIDDEN, "").await;
+    assert!(matches!(err, PlexApiError::Forbidden(_)));
+    assert!(err.is_auth_error());
+
+    let err = connect_to_fake(StatusCode::CONFLICT, "").await;
+    assert!(matches!(err, PlexApiError::UnexpectedStatus(_)));
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn my_plex_xml_errors_are_parsed() {
+    let body = ErrorBody::parse(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<errors>
+  <error code="1001" message="User could not be authenticated"/>
+</errors>"#,
+    );
+    assert_eq!(1, body.get_errors().len());
+    assert_eq!(Some(1001), body.get_errors()[0].get_code());
+    assert_eq!("#1001 - User could not be authenticated", body.to_string());
+}
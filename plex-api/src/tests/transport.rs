@@ -0,0 +1,101 @@
+use crate::{ClientBuilder, HttpTransport, PlexClient, Request, Response, Result, Server};
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub(crate) const SERVER_XML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<MediaContainer size="1" allowCameraUpload="1" allowChannelAccess="1" allowSharing="1" allowSync="1" backgroundProcessing="1" companionProxy="1" eventStream="1" friendlyName="fake" hubSearch="1" itemClusters="1" livetv="7" machineIdentifier="376D9976F5166" photoAutoTag="1" platform="Linux" platformVersion="5.4" pluginHost="1" readOnlyLibraries="0" requestParametersInCookie="1" streamingBrainVersion="2" sync="1" transcoderActiveVideoSessions="0" transcoderAudio="1" transcoderLyrics="1" transcoderPhoto="1" transcoderSubtitles="1" transcoderVideo="1" updater="1" version="1.21.3.4021-5a0a3e4b2" voiceSearch="1">
+    <Directory count="1" key="library" title="library" />
+</MediaContainer>"##;
+
+const SECTIONS_JSON: &str = r##"{"MediaContainer":{"size":1,"allowSync":false,"identifier":"com.plexapp.plugins.library","mediaTagPrefix":"/system/bundle/media/flags/","mediaTagVersion":1591221769,"title1":"Plex Library","Directory":[{"allowSync":true,"art":"/:/resources/movie-fanart.jpg","composite":"/library/sections/1/composite/1591467759","filters":true,"refreshing":false,"thumb":"/:/resources/movie.png","key":"1","type":"movie","title":"Movies","agent":"com.plexapp.agents.imdb","scanner":"Plex Movie Scanner","language":"en","uuid":"5ac3b52d-e852-488e-9f9c-ca6988daa269","updatedAt":1591477261,"createdAt":1506579881,"scannedAt":1591467759,"content":true,"directory":true,"contentChangedAt":1901321,"hidden":0,"Location":[{"id":1,"path":"/data/Movies"}]}]}}"##;
+
+/// Transport, which responds with prepared bodies by request path and remembers every request.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FakeTransport {
+    responses: HashMap<String, (StatusCode, String)>,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl FakeTransport {
+    pub(crate) fn respond(mut self, path: &str, status: StatusCode, body: &str) -> Self {
+        self.responses
+            .insert(path.to_owned(), (status, body.to_owned()));
+        self
+    }
+
+    pub(crate) fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for FakeTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let path = request.get_url().path().to_owned();
+        self.requests.lock().unwrap().push(request);
+        Ok(match self.responses.get(&path) {
+            Some((status, body)) => Response::new(*status, HeaderMap::new(), body.clone().into()),
+            None => Response::new(StatusCode::NOT_FOUND, HeaderMap::new(), vec![]),
+        })
+    }
+}
+
+fn client_with(transport: &FakeTransport) -> PlexClient {
+    ClientBuilder::default()
+        .client_identifier("fake-client")
+        .transport(transport.clone())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn server_connects_through_custom_transport() {
+    let transport = FakeTransport::default().respond("/", StatusCode::OK, SERVER_XML);
+    let client = client_with(&transport);
+
+    let srv = Server::connect_with_client(&client, "http://plex.local:32400/", "token").await;
+    assert!(srv.is_ok(), "Unable to connect to server: {:?}", srv.err());
+
+    let requests = transport.requests();
+    assert_eq!(1, requests.len());
+    let headers = requests[0].get_headers();
+    assert_eq!("token", headers.get("x-plex-token").unwrap());
+    assert_eq!(
+        "fake-client",
+        headers.get("x-plex-client-identifier").unwrap()
+    );
+}
+
+#[tokio::test]
+async fn library_loads_through_custom_transport() {
+    let transport = FakeTransport::default()
+        .respond("/", StatusCode::OK, SERVER_XML)
+        .respond("/library/sections", StatusCode::OK, SECTIONS_JSON);
+    let client = client_with(&transport);
+
+    let srv = Server::connect_with_client(&client, "http://plex.local:32400/", "token")
+        .await
+        .unwrap();
+    let library = srv.get_sections().await;
+    assert!(
+        library.is_ok(),
+        "Unable to get library: {:?}",
+        library.err()
+    );
+
+    let library = library.unwrap();
+    let directory = library.get_directory().as_ref().unwrap();
+    assert_eq!("Movies", directory[0].title);
+}
+
+#[tokio::test]
+async fn unexpected_status_is_reported() {
+    let transport = FakeTransport::default();
+    let client = client_with(&transport);
+
+    let srv = Server::connect_with_client(&client, "http://plex.local:32400/", "token").await;
+    assert!(srv.is_err(), "Connected to a missing server");
+}
@@ -6,6 +6,7 @@ extern crate clap;
 mod subcommands;
 
 use clap::App;
+use plex_api::ClientBuilder;
 use std::env;
 use subcommands::*;
 
@@ -31,24 +32,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .version(env!("CARGO_PKG_VERSION"))
         .get_matches();
 
-    let client_id = env::var("X_PLEX_CLIENT_IDENTIFIER");
-    if let Ok(client_id) = client_id {
-        use plex_api::X_PLEX_CLIENT_IDENTIFIER;
-        let mut client_identifier = X_PLEX_CLIENT_IDENTIFIER.write().unwrap();
-        *client_identifier = client_id;
+    let mut client_builder = ClientBuilder::default();
+    if let Ok(client_id) = env::var("X_PLEX_CLIENT_IDENTIFIER") {
+        client_builder = client_builder.client_identifier(&client_id);
     }
+    let client = client_builder.build()?;
 
     let token_from_env = std::env::var("PLEX_API_AUTH_TOKEN").unwrap_or_else(|_| String::from(""));
     let auth_token = matches.value_of("auth-token").unwrap_or(&token_from_env);
 
     match matches.subcommand() {
-        ("wait", subcommand_matches) => subcommand_wait(auth_token, subcommand_matches).await,
+        ("wait", subcommand_matches) => {
+            subcommand_wait(&client, auth_token, subcommand_matches).await
+        }
         ("settings", subcommand_matches) => {
-            subcommand_settings(auth_token, subcommand_matches).await
+            subcommand_settings(&client, auth_token, subcommand_matches).await
+        }
+        ("token", subcommand_matches) => {
+            subcommand_token(&client, auth_token, subcommand_matches).await
+        }
+        ("unclaim", subcommand_matches) => {
+            subcommand_unclaim(&client, auth_token, subcommand_matches).await
+        }
+        ("claim", subcommand_matches) => {
+            subcommand_claim(&client, auth_token, subcommand_matches).await
         }
-        ("token", subcommand_matches) => subcommand_token(auth_token, subcommand_matches).await,
-        ("unclaim", subcommand_matches) => subcommand_unclaim(auth_token, subcommand_matches).await,
-        ("claim", subcommand_matches) => subcommand_claim(auth_token, subcommand_matches).await,
         _ => {
             // Unexpected subcommand called, we shouldn't even be here
             unimplemented!();
@@ -2,12 +2,13 @@ use clap::ArgMatches;
 use plex_api::prelude::*;
 
 pub(crate) async fn subcommand_claim(
+    client: &PlexClient,
     token: &str,
     matches: Option<&ArgMatches<'_>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let matches = matches.unwrap();
     let server_url = matches.value_of("server-url").unwrap();
-    let mut srv = Server::connect(server_url, token).await?;
+    let mut srv = Server::connect_with_client(client, server_url, token).await?;
 
     let claim_token = {
         if let Some(claim_token) = matches.value_of("claim-token") {
@@ -19,7 +20,7 @@ pub(crate) async fn subcommand_claim(
         } else if token.is_empty() {
             panic!("You should specify either `--claim-token` or `--auth-token`");
         } else {
-            match MyPlexAccount::by_token(token).await {
+            match MyPlexAccount::by_token_with_client(client, token).await {
                 Ok(acc) => match acc.get_claim_token().await {
                     Ok(claim_token) => claim_token,
                     Err(e) => return Err(Box::new(e)),
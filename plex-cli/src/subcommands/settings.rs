@@ -21,12 +21,13 @@ enum SubcommandSettingsError {
 }
 
 pub(crate) async fn subcommand_settings(
+    client: &PlexClient,
     token: &str,
     matches: Option<&ArgMatches<'_>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let matches = matches.unwrap();
     let server_url = matches.value_of("server-url").unwrap();
-    let srv = Server::connect(server_url, token).await?;
+    let srv = Server::connect_with_client(client, server_url, token).await?;
 
     let setting_name = matches.value_of("name").unwrap();
 
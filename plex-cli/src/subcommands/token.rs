@@ -2,10 +2,11 @@ use clap::ArgMatches;
 use plex_api::prelude::*;
 
 pub(crate) async fn subcommand_token(
+    client: &PlexClient,
     token: &str,
     _matches: Option<&ArgMatches<'_>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let my_plex = MyPlexAccount::by_token(token).await?;
+    let my_plex = MyPlexAccount::by_token_with_client(client, token).await?;
     let claim_token = my_plex.get_claim_token().await?;
     println!("{}", claim_token);
     Ok(())
@@ -1,47 +1,49 @@
 use clap::ArgMatches;
 use plex_api::prelude::*;
-use std::thread;
 use std::time::Duration;
 use tokio::time;
 
 async fn wait(
+    client: &PlexClient,
     token: &str,
     server_url: &str,
     wait_for_settings: bool,
     delay: &Duration,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    // Connection errors and unavailability of the starting server are retried by the client,
+    // any other error is reported and the server is polled again until the timeout is reached
+    let client = client.with_retry_policy(
+        RetryPolicy::default()
+            .max_attempts(u32::MAX)
+            .initial_delay(*delay)
+            .max_delay(*delay)
+            .jitter(false),
+    );
+
     loop {
-        let srv = Server::connect(server_url, token).await;
-        match srv {
+        match Server::connect_with_client(&client, server_url, token).await {
             Ok(srv) => {
-                if wait_for_settings {
-                    match srv.get_settings().await {
-                        Ok(settings) => match settings.get("AcceptedEULA") {
-                            Ok(..) => {
-                                break;
-                            }
-                            Err(e) => {
-                                eprintln!("AcceptedEULA setting not found: {}", e);
-                            }
-                        },
-                        Err(e) => {
-                            eprintln!("Unable to get settings: {}", e);
-                        }
-                    }
-                } else {
+                if !wait_for_settings {
                     break;
                 }
+
+                match srv.get_settings().await {
+                    Ok(settings) => match settings.get("AcceptedEULA") {
+                        Ok(..) => break,
+                        Err(e) => eprintln!("AcceptedEULA setting not found: {}", e),
+                    },
+                    Err(e) => eprintln!("Unable to get settings: {}", e),
+                }
             }
-            Err(e) => {
-                eprintln!("Failed to connect to the server: {}", e);
-            }
+            Err(e) => eprintln!("Failed to connect to the server: {}", e),
         }
-        thread::sleep(*delay);
+        time::sleep(*delay).await;
     }
     Ok(())
 }
 
 pub(crate) async fn subcommand_wait(
+    client: &PlexClient,
     token: &str,
     matches: Option<&ArgMatches<'_>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -51,7 +53,11 @@ pub(crate) async fn subcommand_wait(
     let wait_for_settings = matches.is_present("wait-for-settings");
     let delay = Duration::from_secs(matches.value_of("delay").unwrap_or("3").parse().unwrap());
 
-    let result = time::timeout(timeout, wait(token, server_url, wait_for_settings, &delay)).await;
+    let result = time::timeout(
+        timeout,
+        wait(client, token, server_url, wait_for_settings, &delay),
+    )
+    .await;
 
     if result.is_err() {
         Err(Box::new(result.err().unwrap()))
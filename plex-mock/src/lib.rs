@@ -0,0 +1,124 @@
+//! Offline mock of Plex Media Server and plex.tv.
+//!
+//! The server binds to localhost and serves recorded fixtures for the endpoints used by
+//! `plex-api`, keeping the state of claiming, settings, privacy and webhooks between requests.
+//! Both Plex Media Server and plex.tv endpoints are served from the same address, so the mock's
+//! [`url`] should be used as the server's url, and as the MyPlex url of the client.
+//!
+//! [`url`]: struct.MockServer.html#method.url
+
+mod routes;
+mod state;
+
+use hyper::service::{make_service_fn, service_fn};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+use state::State;
+
+/// Username, accepted by the mock's sign-in endpoint.
+pub const USERNAME: &str = "mock-user";
+/// Password, accepted by the mock's sign-in endpoint.
+pub const PASSWORD: &str = "mock-password";
+/// Username of the account with two-factor authentication, it uses the same [`PASSWORD`].
+///
+/// [`PASSWORD`]: constant.PASSWORD.html
+pub const OTP_USERNAME: &str = "mock-otp-user";
+/// Verification code, accepted for the account with two-factor authentication.
+pub const VERIFICATION_CODE: &str = "123456";
+/// PIN of the protected managed user in the mock's Plex Home.
+pub const HOME_USER_PIN: &str = "1234";
+/// Authentication token of the mock's MyPlex account.
+pub const AUTH_TOKEN: &str = "mock-auth-token";
+/// Claim token, accepted by the mock's claim endpoint and returned by plex.tv's one.
+pub const CLAIM_TOKEN: &str = "claim-mock-token";
+/// Number of items in every section of the mocked library, served page by page from
+/// `library/sections/{key}/all`.
+pub const SECTION_SIZE: u32 = 250;
+/// `machineIdentifier` of the mocked Plex Media Server.
+pub const MACHINE_IDENTIFIER: &str = "376D9976F5166";
+
+/// A running mock server, it's stopped when the value is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Starts the mock on a random port of localhost.
+    pub async fn start() -> hyper::Result<Self> {
+        Self::bind(([127, 0, 0, 1], 0).into()).await
+    }
+
+    /// Starts the mock on the provided address.
+    pub async fn bind(addr: SocketAddr) -> hyper::Result<Self> {
+        let builder = hyper::Server::try_bind(&addr)?;
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    routes::handle(state.clone(), request)
+                }))
+            }
+        });
+
+        let server = builder.serve(make_service);
+        let addr = server.local_addr();
+        state
+            .lock()
+            .unwrap()
+            .set_base_url(&format!("http://{}/", addr));
+
+        let (shutdown, rx) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            rx.await.ok();
+        }));
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Returns the address the mock is listening on.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the base url of the mock, e.g. `http://127.0.0.1:32400/`.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Returns `true` if the mocked server is claimed at the moment.
+    pub fn is_claimed(&self) -> bool {
+        self.state.lock().unwrap().is_claimed()
+    }
+
+    /// Links the PIN with the provided code to the mock's account, like the user would do by
+    /// signing in at `app.plex.tv/auth`. Returns `false` if the PIN is unknown.
+    pub fn link_pin(&self, code: &str) -> bool {
+        self.state.lock().unwrap().link_pin(code)
+    }
+
+    /// Makes the PIN with the provided code expired, after that it isn't found anymore.
+    /// Returns `false` if the PIN is unknown.
+    pub fn expire_pin(&self, code: &str) -> bool {
+        self.state.lock().unwrap().expire_pin(code)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use hyper::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use std::collections::hash_map::DefaultHasher;
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use crate::state::{home_user_token, State};
+use crate::{
+    AUTH_TOKEN, CLAIM_TOKEN, HOME_USER_PIN, MACHINE_IDENTIFIER, OTP_USERNAME, PASSWORD,
+    SECTION_SIZE, USERNAME, VERIFICATION_CODE,
+};
+
+const LIBRARY_SECTIONS_JSON: &str = include_str!("../fixtures/library_sections.json");
+const LIBRARY_RECENTLY_ADDED_JSON: &str = include_str!("../fixtures/library_recently_added.json");
+const LIBRARY_ON_DECK_JSON: &str = include_str!("../fixtures/library_on_deck.json");
+const DEVICES_JSON: &str = include_str!("../fixtures/devices.json");
+const RESOURCES_JSON: &str = include_str!("../fixtures/resources.json");
+
+const UNAUTHORIZED_HTML: &str =
+    "<html><head><title>Unauthorized</title></head><body><h1>401 Unauthorized</h1></body></html>";
+
+const CONTENT_TYPE_JSON: &str = "application/json";
+const CONTENT_TYPE_XML: &str = "text/xml;charset=utf-8";
+
+pub(crate) async fn handle(
+    state: Arc<Mutex<State>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+
+    let mut params: Vec<(String, String)> = parts
+        .uri
+        .query()
+        .map(|q| {
+            url::form_urlencoded::parse(q.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default();
+    params.extend(url::form_urlencoded::parse(&body).into_owned());
+
+    let token = parts
+        .headers
+        .get("X-Plex-Token")
+        .and_then(|t| t.to_str().ok())
+        .unwrap_or_default();
+
+    let client_identifier = parts
+        .headers
+        .get("X-Plex-Client-Identifier")
+        .and_then(|t| t.to_str().ok())
+        .unwrap_or_default();
+
+    let mut state = state.lock().unwrap();
+    let path = parts.uri.path();
+    // Plex Media Server responds with XML, unless JSON is requested explicitly
+    let media_container = if accepts_json(&parts.headers) {
+        json
+    } else {
+        media_container_xml
+    };
+
+    let response = match (&parts.method, path) {
+        // Plex Media Server
+        _ if !token.is_empty()
+            && token != AUTH_TOKEN
+            && !(path.starts_with("/api/") || path == "/devices.xml") =>
+        {
+            with_status(
+                StatusCode::UNAUTHORIZED,
+                with_content_type("text/html", UNAUTHORIZED_HTML.to_owned()),
+            )
+        }
+        (&Method::GET, "/") => media_container(state.server()),
+        (&Method::GET, "/:/prefs") => {
+            conditional(&parts.headers, media_container, state.prefs().to_string())
+        }
+        (&Method::PUT, "/:/prefs") => {
+            let updated = params
+                .iter()
+                .all(|(key, value)| state.update_setting(key, value));
+            if updated {
+                empty(StatusCode::OK)
+            } else {
+                empty(StatusCode::BAD_REQUEST)
+            }
+        }
+        (&Method::GET, "/library/sections") => conditional(
+            &parts.headers,
+            media_container,
+            state.render(LIBRARY_SECTIONS_JSON),
+        ),
+        (&Method::GET, _) if path.starts_with("/library/sections/") && path.ends_with("/all") => {
+            let start = container_param(&parts.headers, &params, "X-Plex-Container-Start");
+            let size = container_param(&parts.headers, &params, "X-Plex-Container-Size");
+            media_container(section_page(
+                &state.render(LIBRARY_RECENTLY_ADDED_JSON),
+                start,
+                size,
+            ))
+        }
+        (&Method::GET, "/library/recentlyAdded") => {
+            media_container(state.render(LIBRARY_RECENTLY_ADDED_JSON))
+        }
+        (&Method::GET, "/library/onDeck") => media_container(state.render(LIBRARY_ON_DECK_JSON)),
+        (&Method::POST, "/myplex/claim") => {
+            if param(&params, "token") == Some(CLAIM_TOKEN) {
+                state.claim();
+                empty(StatusCode::OK)
+            } else {
+                text(StatusCode::BAD_REQUEST, "Invalid claim token")
+            }
+        }
+        (&Method::DELETE, "/myplex/account") => {
+            state.unclaim();
+            empty(StatusCode::OK)
+        }
+
+        // plex.tv
+        (&Method::POST, "/api/v2/users/signin") => {
+            let login = param(&params, "login");
+            let verification_code = param(&params, "verificationCode");
+            if param(&params, "password") != Some(PASSWORD) {
+                my_plex_error(
+                    StatusCode::UNAUTHORIZED,
+                    1001,
+                    "User could not be authenticated",
+                )
+            } else if login == Some(OTP_USERNAME) && verification_code.is_none() {
+                my_plex_error(
+                    StatusCode::UNAUTHORIZED,
+                    1029,
+                    "Please enter the verification code",
+                )
+            } else if login == Some(OTP_USERNAME) && verification_code != Some(VERIFICATION_CODE) {
+                my_plex_error(StatusCode::UNAUTHORIZED, 1030, "Invalid verification code")
+            } else if login == Some(USERNAME) || login == Some(OTP_USERNAME) {
+                state.sign_in();
+                with_status(StatusCode::CREATED, json(state.account()))
+            } else {
+                my_plex_error(
+                    StatusCode::UNAUTHORIZED,
+                    1001,
+                    "User could not be authenticated",
+                )
+            }
+        }
+        (&Method::POST, "/api/v2/pins") => {
+            if client_identifier.is_empty() {
+                my_plex_error(
+                    StatusCode::BAD_REQUEST,
+                    1000,
+                    "X-Plex-Client-Identifier is missing",
+                )
+            } else {
+                with_status(
+                    StatusCode::CREATED,
+                    json(state.create_pin(client_identifier).to_string()),
+                )
+            }
+        }
+        (&Method::GET, _) if path.starts_with("/api/v2/pins/") => {
+            match state.find_pin(&path["/api/v2/pins/".len()..], client_identifier) {
+                Some(pin) => json(pin.to_string()),
+                None => my_plex_error(StatusCode::NOT_FOUND, 1020, "Code not found or expired"),
+            }
+        }
+        _ if !state.is_authorized(token)
+            && (path.starts_with("/api/") || path == "/devices.xml") =>
+        {
+            my_plex_error(
+                StatusCode::UNAUTHORIZED,
+                1001,
+                "User could not be authenticated",
+            )
+        }
+        (&Method::DELETE, "/api/v2/users/signout") => {
+            state.sign_out();
+            empty(StatusCode::NO_CONTENT)
+        }
+        (&Method::GET, "/devices.xml") => media_container(state.render(DEVICES_JSON)),
+        (&Method::GET, "/api/v2/user") => json(state.account_for(token)),
+        (&Method::GET, "/api/claim/token.json") => {
+            json(serde_json::json!({ "token": CLAIM_TOKEN }).to_string())
+        }
+        (&Method::GET, "/api/resources") => conditional(
+            &parts.headers,
+            media_container,
+            state.render(RESOURCES_JSON),
+        ),
+        (&Method::GET, "/api/users/") => media_container(state.users()),
+        (&Method::PUT, _) if path_param(path, "/api/friends/", "").is_some() => {
+            let attributes: Vec<_> = params
+                .iter()
+                .filter(|(key, _)| key.starts_with("allow") || key.starts_with("filter"))
+                .cloned()
+                .collect();
+            if state.update_friend(&path["/api/friends/".len()..], &attributes) {
+                empty(StatusCode::OK)
+            } else {
+                my_plex_error(StatusCode::NOT_FOUND, 1002, "User not found")
+            }
+        }
+        (&Method::DELETE, _) if path_param(path, "/api/friends/", "").is_some() => {
+            if state.remove_friend(&path["/api/friends/".len()..]) {
+                empty(StatusCode::OK)
+            } else {
+                my_plex_error(StatusCode::NOT_FOUND, 1002, "User not found")
+            }
+        }
+        (&Method::GET, _) if path_param(path, "/api/servers/", "") == Some(MACHINE_IDENTIFIER) => {
+            media_container(state.server_sections())
+        }
+        (&Method::GET, _)
+            if path_param(path, "/api/servers/", "/shared_servers") == Some(MACHINE_IDENTIFIER) =>
+        {
+            media_container(shared_servers(state.shared_servers()))
+        }
+        (&Method::POST, _)
+            if path_param(path, "/api/servers/", "/shared_servers") == Some(MACHINE_IDENTIFIER) =>
+        {
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+            let shared_server = &request["shared_server"];
+            match shared_server["invited_email"].as_str() {
+                Some(invited) if !invited.is_empty() => {
+                    let created = state.share_server(
+                        invited,
+                        &section_ids(&shared_server["library_section_ids"]),
+                        &request["sharing_settings"],
+                    );
+                    media_container(shared_servers(std::slice::from_ref(created)))
+                }
+                _ => my_plex_error(StatusCode::BAD_REQUEST, 1000, "invited_email is missing"),
+            }
+        }
+        (&Method::PUT, _)
+            if path.starts_with("/api/servers/") && path.contains("/shared_servers/") =>
+        {
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+            let id = &path[path.rfind('/').unwrap() + 1..];
+            let ids = section_ids(&request["shared_server"]["library_section_ids"]);
+            let updated = state.update_shared_sections(id, &ids);
+            if updated {
+                empty(StatusCode::OK)
+            } else {
+                my_plex_error(StatusCode::NOT_FOUND, 1002, "Shared server not found")
+            }
+        }
+        (&Method::GET, "/api/invites/requests") => media_container(state.invites()),
+        (&Method::GET, "/api/invites/requested") => media_container(state.sent_invites()),
+        (&Method::PUT, _) | (&Method::DELETE, _)
+            if path_param(path, "/api/invites/requests/", "").is_some() =>
+        {
+            if !has_invite_flags(parts.uri.query()) {
+                my_plex_error(StatusCode::BAD_REQUEST, 1000, "Invite flags are missing")
+            } else if state.remove_invite(&path["/api/invites/requests/".len()..]) {
+                empty(StatusCode::OK)
+            } else {
+                my_plex_error(StatusCode::NOT_FOUND, 1002, "Invite not found")
+            }
+        }
+        (&Method::DELETE, _) if path_param(path, "/api/invites/requested/", "").is_some() => {
+            if !has_invite_flags(parts.uri.query()) {
+                my_plex_error(StatusCode::BAD_REQUEST, 1000, "Invite flags are missing")
+            } else if state.remove_sent_invite(&path["/api/invites/requested/".len()..]) {
+                empty(StatusCode::OK)
+            } else {
+                my_plex_error(StatusCode::NOT_FOUND, 1002, "Invite not found")
+            }
+        }
+        (&Method::GET, "/api/v2/user/profile") => json(state.profile().to_string()),
+        (&Method::PUT, "/api/v2/user/profile") => {
+            let updated = params
+                .iter()
+                .all(|(key, value)| state.update_profile(key, value));
+            if updated {
+                empty(StatusCode::NO_CONTENT)
+            } else {
+                my_plex_error(StatusCode::BAD_REQUEST, 1000, "Invalid profile setting")
+            }
+        }
+        (&Method::GET, "/api/v2/user/privacy") => json(state.privacy().to_string()),
+        (&Method::PUT, "/api/v2/user/privacy") => {
+            state.set_privacy(
+                param(&params, "optOutPlayback") == Some("1"),
+                param(&params, "optOutLibraryStats") == Some("1"),
+            );
+            empty(StatusCode::NO_CONTENT)
+        }
+        (&Method::GET, "/api/v2/user/webhooks") => {
+            let webhooks: Vec<_> = state
+                .webhooks()
+                .iter()
+                .map(|url| serde_json::json!({ "url": url }))
+                .collect();
+            json(serde_json::Value::from(webhooks).to_string())
+        }
+        (&Method::POST, "/api/v2/user/webhooks") => {
+            state.set_webhooks(
+                params
+                    .into_iter()
+                    .filter(|(key, value)| key == "urls[]" && !value.is_empty())
+                    .map(|(_, value)| value)
+                    .collect(),
+            );
+            empty(StatusCode::CREATED)
+        }
+        (&Method::GET, "/api/home/users") => media_container(state.home_users()),
+        (&Method::POST, "/api/v2/home/users/restricted") => match param(&params, "name") {
+            Some(name) if !name.is_empty() => {
+                let user = state.create_home_user(name, param(&params, "restrictionProfile"));
+                with_status(StatusCode::CREATED, json(user.to_string()))
+            }
+            _ => my_plex_error(StatusCode::BAD_REQUEST, 1000, "Name is missing"),
+        },
+        (&Method::POST, _) if path.starts_with("/api/home/users/") && path.ends_with("/switch") => {
+            let id = &path["/api/home/users/".len()..path.len() - "/switch".len()];
+            match state.find_home_user(id) {
+                Some(user)
+                    if user["protected"] == true
+                        && param(&params, "pin") != Some(HOME_USER_PIN) =>
+                {
+                    my_plex_error(StatusCode::UNAUTHORIZED, 1041, "Invalid PIN")
+                }
+                Some(user) => {
+                    let mut switched = user.clone();
+                    switched["authenticationToken"] = home_user_token(user).into();
+                    let mut body = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+                    write_element(&mut body, "user", &switched);
+                    xml(state.render(&body))
+                }
+                None => my_plex_error(StatusCode::NOT_FOUND, 1002, "User not found"),
+            }
+        }
+        (&Method::DELETE, _) if path.starts_with("/api/home/users/") => {
+            if state.remove_home_user(&path["/api/home/users/".len()..]) {
+                empty(StatusCode::NO_CONTENT)
+            } else {
+                my_plex_error(StatusCode::NOT_FOUND, 1002, "User not found")
+            }
+        }
+        _ => empty(StatusCode::NOT_FOUND),
+    };
+
+    Ok(response)
+}
+
+/// Returns the part of the path between the prefix and the suffix, if it doesn't contain
+/// other segments.
+fn path_param<'a>(path: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    path.strip_prefix(prefix)?
+        .strip_suffix(suffix)
+        .filter(|param| !param.is_empty() && !param.contains('/'))
+}
+
+/// Checks that the kind of the invite is sent in the query string, plex.tv ignores it in the
+/// request body.
+fn has_invite_flags(query: Option<&str>) -> bool {
+    let params: Vec<_> = url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+        .map(|(key, _)| key)
+        .collect();
+    ["friend", "server", "home"]
+        .iter()
+        .all(|flag| params.iter().any(|key| key == flag))
+}
+
+fn shared_servers(shared_servers: &[serde_json::Value]) -> String {
+    serde_json::json!({
+        "MediaContainer": {
+            "friendlyName": "myPlex",
+            "identifier": "com.plexapp.plugins.myplex",
+            "machineIdentifier": MACHINE_IDENTIFIER,
+            "size": shared_servers.len(),
+            "SharedServer": shared_servers,
+        }
+    })
+    .to_string()
+}
+
+fn section_ids(value: &serde_json::Value) -> Vec<u64> {
+    value
+        .as_array()
+        .map(|ids| ids.iter().filter_map(|id| id.as_u64()).collect())
+        .unwrap_or_default()
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Returns paging parameter from the headers or from the query, like Plex Media Server does.
+fn container_param(headers: &HeaderMap, params: &[(String, String)], name: &str) -> Option<u32> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| param(params, name))
+        .and_then(|v| v.parse().ok())
+}
+
+/// Builds a page of a section, filled with copies of the first item of the fixture.
+fn section_page(fixture: &str, start: Option<u32>, size: Option<u32>) -> String {
+    let mut container: serde_json::Value = serde_json::from_str(fixture).unwrap();
+    let template = container["MediaContainer"]["Metadata"][0].clone();
+
+    let start = start.unwrap_or(0).min(SECTION_SIZE);
+    let end = size.map_or(SECTION_SIZE, |size| (start + size).min(SECTION_SIZE));
+    let items: Vec<_> = (start..end)
+        .map(|i| {
+            let mut item = template.clone();
+            item["ratingKey"] = serde_json::Value::from(i.to_string());
+            item["key"] = serde_json::Value::from(format!("/library/metadata/{}", i));
+            item
+        })
+        .collect();
+
+    let mc = &mut container["MediaContainer"];
+    mc["size"] = serde_json::Value::from(items.len());
+    mc["offset"] = serde_json::Value::from(start);
+    mc["totalSize"] = serde_json::Value::from(SECTION_SIZE);
+    mc["Metadata"] = serde_json::Value::from(items);
+    container.to_string()
+}
+
+fn with_content_type(content_type: &'static str, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+/// Responds with `ETag`, or with `304 Not Modified` when the client already has the body.
+fn conditional(
+    headers: &HeaderMap,
+    render: fn(String) -> Response<Body>,
+    body: String,
+) -> Response<Body> {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    let etag = HeaderValue::from_str(&format!("\"{:x}\"", hasher.finish())).unwrap();
+
+    let mut response = if headers.get(IF_NONE_MATCH) == Some(&etag) {
+        empty(StatusCode::NOT_MODIFIED)
+    } else {
+        render(body)
+    };
+    response.headers_mut().insert(ETAG, etag);
+    response
+}
+
+fn json(body: String) -> Response<Body> {
+    with_content_type(CONTENT_TYPE_JSON, body)
+}
+
+fn xml(body: String) -> Response<Body> {
+    with_content_type(CONTENT_TYPE_XML, body)
+}
+
+fn accepts_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("json"))
+}
+
+/// Renders `{"MediaContainer": {...}}` JSON the way Plex Media Server renders XML: scalar values
+/// become attributes and objects become child elements, named after their keys.
+fn media_container_xml(body: String) -> Response<Body> {
+    let container: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    write_element(&mut xml, "MediaContainer", &container["MediaContainer"]);
+    with_content_type(CONTENT_TYPE_XML, xml)
+}
+
+fn write_element(xml: &mut String, name: &str, value: &serde_json::Value) {
+    use serde_json::Value;
+
+    let empty = serde_json::Map::new();
+    let fields = value.as_object().unwrap_or(&empty);
+
+    xml.push('<');
+    xml.push_str(name);
+    for (key, value) in fields {
+        let value = match value {
+            Value::Bool(b) => String::from(if *b { "1" } else { "0" }),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => s.clone(),
+            _ => continue,
+        };
+        xml.push_str(&format!(r#" {}="{}""#, key, escape_xml(&value)));
+    }
+    xml.push('>');
+
+    for (key, value) in fields {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .filter(|item| item.is_object())
+                .for_each(|item| write_element(xml, key, item)),
+            Value::Object(_) => write_element(xml, key, value),
+            _ => {}
+        }
+    }
+
+    xml.push_str(&format!("</{}>", name));
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn text(status: StatusCode, body: &str) -> Response<Body> {
+    with_status(status, Response::new(Body::from(body.to_owned())))
+}
+
+fn empty(status: StatusCode) -> Response<Body> {
+    with_status(status, Response::new(Body::empty()))
+}
+
+fn with_status(status: StatusCode, mut response: Response<Body>) -> Response<Body> {
+    *response.status_mut() = status;
+    response
+}
+
+fn my_plex_error(status: StatusCode, code: i32, message: &str) -> Response<Body> {
+    let body = serde_json::json!({ "errors": [{ "code": code, "message": message }] });
+    with_status(status, json(body.to_string()))
+}
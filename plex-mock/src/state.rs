@@ -0,0 +1,540 @@
+use serde_json::Value;
+
+use crate::{AUTH_TOKEN, USERNAME};
+
+const SERVER_JSON: &str = include_str!("../fixtures/server.json");
+const PREFS_JSON: &str = include_str!("../fixtures/prefs.json");
+const PRIVACY_JSON: &str = include_str!("../fixtures/privacy.json");
+const ACCOUNT_JSON: &str = include_str!("../fixtures/account.json");
+const PIN_JSON: &str = include_str!("../fixtures/pin.json");
+const HOME_USERS_JSON: &str = include_str!("../fixtures/home_users.json");
+const USERS_JSON: &str = include_str!("../fixtures/users.json");
+const SERVER_SECTIONS_JSON: &str = include_str!("../fixtures/server_sections.json");
+const INVITES_JSON: &str = include_str!("../fixtures/invites.json");
+
+/// Returns the token, which is issued when switching to the Home user. The admin gets the mock's
+/// own token.
+pub(crate) fn home_user_token(user: &Value) -> String {
+    if user["admin"] == true {
+        AUTH_TOKEN.to_owned()
+    } else {
+        format!("home-user-token-{}", user["id"])
+    }
+}
+
+pub(crate) struct State {
+    base_url: String,
+    claimed: bool,
+    prefs: Value,
+    privacy: Value,
+    webhooks: Vec<String>,
+    pins: Vec<Value>,
+    last_pin_id: u64,
+    signed_out: bool,
+    home_users: Value,
+    users: Value,
+    shared_servers: Vec<Value>,
+    invites: Value,
+    sent_invites: Vec<Value>,
+    profile: Value,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            base_url: String::new(),
+            claimed: false,
+            prefs: serde_json::from_str(PREFS_JSON).expect("prefs.json fixture"),
+            privacy: serde_json::from_str(PRIVACY_JSON).expect("privacy.json fixture"),
+            webhooks: vec![],
+            pins: vec![],
+            last_pin_id: 0,
+            signed_out: false,
+            home_users: serde_json::from_str(HOME_USERS_JSON).expect("home_users.json fixture"),
+            users: serde_json::from_str(USERS_JSON).expect("users.json fixture"),
+            shared_servers: vec![],
+            invites: serde_json::from_str(INVITES_JSON).expect("invites.json fixture"),
+            sent_invites: vec![],
+            profile: serde_json::from_str::<Value>(ACCOUNT_JSON).expect("account.json fixture")
+                ["profile"]
+                .take(),
+        }
+    }
+}
+
+impl State {
+    pub(crate) fn set_base_url(&mut self, base_url: &str) {
+        self.base_url = base_url.to_owned();
+    }
+
+    /// Replaces placeholders, which can be used in any fixture.
+    pub(crate) fn render(&self, fixture: &str) -> String {
+        fixture
+            .replace("{{base_url}}", &self.base_url)
+            .replace("{{username}}", USERNAME)
+            .replace("{{auth_token}}", AUTH_TOKEN)
+    }
+
+    pub(crate) const fn is_claimed(&self) -> bool {
+        self.claimed
+    }
+
+    pub(crate) fn claim(&mut self) {
+        self.claimed = true;
+    }
+
+    pub(crate) fn unclaim(&mut self) {
+        self.claimed = false;
+    }
+
+    pub(crate) fn server(&self) -> String {
+        let mut server: Value = serde_json::from_str(&self.render(SERVER_JSON)).unwrap();
+        let container = &mut server["MediaContainer"];
+        container["friendlyName"] = Value::from(self.friendly_name());
+        if self.claimed {
+            container["myPlex"] = Value::Bool(true);
+            container["myPlexMappingState"] = Value::from("mapped");
+            container["myPlexSigninState"] = Value::from("ok");
+            container["myPlexSubscription"] = Value::Bool(true);
+            container["myPlexUsername"] = Value::from(USERNAME);
+        } else {
+            container["myPlex"] = Value::Bool(false);
+            container["myPlexMappingState"] = Value::from("unknown");
+            container["myPlexSigninState"] = Value::from("none");
+        }
+        server.to_string()
+    }
+
+    fn friendly_name(&self) -> String {
+        self.find_setting("FriendlyName")
+            .and_then(|s| s["value"].as_str())
+            .unwrap_or("plex-mock")
+            .to_owned()
+    }
+
+    pub(crate) const fn prefs(&self) -> &Value {
+        &self.prefs
+    }
+
+    fn find_setting(&self, id: &str) -> Option<&Value> {
+        self.prefs["MediaContainer"]["Setting"]
+            .as_array()?
+            .iter()
+            .find(|s| s["id"] == id)
+    }
+
+    /// Updates a setting, converting provided value according to the setting's type. Returns
+    /// `false` if the setting is unknown or the value can't be converted.
+    pub(crate) fn update_setting(&mut self, id: &str, value: &str) -> bool {
+        let setting = self.prefs["MediaContainer"]["Setting"]
+            .as_array_mut()
+            .and_then(|settings| settings.iter_mut().find(|s| s["id"] == id));
+
+        let setting = match setting {
+            Some(setting) => setting,
+            None => return false,
+        };
+
+        let new_value = match setting["type"].as_str() {
+            Some("bool") => match value {
+                "1" | "true" => Value::Bool(true),
+                "0" | "false" => Value::Bool(false),
+                _ => return false,
+            },
+            Some("int") => match value.parse::<i64>() {
+                Ok(v) => Value::from(v),
+                Err(_) => return false,
+            },
+            Some("double") => match value.parse::<f64>() {
+                Ok(v) => Value::from(v),
+                Err(_) => return false,
+            },
+            _ => Value::from(value),
+        };
+
+        setting["value"] = new_value;
+        true
+    }
+
+    pub(crate) fn sign_in(&mut self) {
+        self.signed_out = false;
+    }
+
+    pub(crate) fn sign_out(&mut self) {
+        self.signed_out = true;
+    }
+
+    pub(crate) fn account(&self) -> String {
+        let mut account: Value = serde_json::from_str(&self.render(ACCOUNT_JSON)).unwrap();
+        account["profile"] = self.profile.clone();
+        account.to_string()
+    }
+
+    pub(crate) const fn profile(&self) -> &Value {
+        &self.profile
+    }
+
+    /// Updates the profile, converting provided values to the types used by the fixture.
+    /// Returns `false` if a key is unknown or the value can't be converted.
+    pub(crate) fn update_profile(&mut self, key: &str, value: &str) -> bool {
+        let new_value = match key {
+            "autoSelectAudio" => match value {
+                "1" | "true" => Value::Bool(true),
+                "0" | "false" => Value::Bool(false),
+                _ => return false,
+            },
+            "autoSelectSubtitle" | "defaultSubtitleAccessibility" | "defaultSubtitleForced" => {
+                match value.parse::<u8>() {
+                    Ok(v) => Value::from(v),
+                    Err(_) => return false,
+                }
+            }
+            "defaultAudioLanguage" | "defaultSubtitleLanguage" => Value::from(value),
+            _ => return false,
+        };
+        self.profile[key] = new_value;
+        true
+    }
+
+    /// Returns `true` if requests with the token should be accepted by plex.tv.
+    pub(crate) fn is_authorized(&self, token: &str) -> bool {
+        if token == AUTH_TOKEN {
+            !self.signed_out
+        } else {
+            self.find_home_user_by_token(token).is_some()
+        }
+    }
+
+    /// Returns the account, which the token belongs to: the mock's account or one of Home users.
+    pub(crate) fn account_for(&self, token: &str) -> String {
+        let user = match self.find_home_user_by_token(token) {
+            Some(user) => user,
+            None => return self.account(),
+        };
+
+        let mut account: Value = serde_json::from_str(&self.account()).unwrap();
+        for key in &["id", "uuid", "username", "title", "email", "thumb"] {
+            account[*key] = user[*key].clone();
+        }
+        account["authToken"] = Value::from(token);
+        self.render(&account.to_string())
+    }
+
+    fn home_user_list(&self) -> &Vec<Value> {
+        self.home_users["MediaContainer"]["User"]
+            .as_array()
+            .expect("home users")
+    }
+
+    pub(crate) fn home_users(&self) -> String {
+        let mut home_users = self.home_users.clone();
+        let size = Value::from(self.home_user_list().len());
+        home_users["MediaContainer"]["size"] = size.clone();
+        home_users["MediaContainer"]["totalSize"] = size;
+        self.render(&home_users.to_string())
+    }
+
+    pub(crate) fn find_home_user(&self, id: &str) -> Option<&Value> {
+        let id = id.parse::<u64>().ok();
+        self.home_user_list()
+            .iter()
+            .find(|user| user["id"].as_u64() == id)
+    }
+
+    fn find_home_user_by_token(&self, token: &str) -> Option<&Value> {
+        self.home_user_list().iter().find(|user| {
+            !user["admin"].as_bool().unwrap_or_default() && home_user_token(user) == token
+        })
+    }
+
+    pub(crate) fn create_home_user(
+        &mut self,
+        name: &str,
+        restriction_profile: Option<&str>,
+    ) -> &Value {
+        let id = self
+            .home_user_list()
+            .iter()
+            .filter_map(|user| user["id"].as_u64())
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let uuid = format!("{:016x}", id);
+        let user = serde_json::json!({
+            "id": id,
+            "uuid": uuid,
+            "admin": false,
+            "guest": false,
+            "restricted": true,
+            "restrictionProfile": restriction_profile.unwrap_or("little_kid"),
+            "protected": false,
+            "hasPassword": false,
+            "title": name,
+            "username": "",
+            "email": "",
+            "thumb": format!("https://plex.tv/users/{}/avatar", uuid),
+        });
+
+        let users = self.home_users["MediaContainer"]["User"]
+            .as_array_mut()
+            .expect("home users");
+        users.push(user);
+        users.last().unwrap()
+    }
+
+    pub(crate) fn remove_home_user(&mut self, id: &str) -> bool {
+        let id = id.parse::<u64>().ok();
+        let users = self.home_users["MediaContainer"]["User"]
+            .as_array_mut()
+            .expect("home users");
+        let len = users.len();
+        users.retain(|user| user["id"].as_u64() != id || user["admin"] == true);
+        users.len() != len
+    }
+
+    pub(crate) const fn privacy(&self) -> &Value {
+        &self.privacy
+    }
+
+    pub(crate) fn set_privacy(&mut self, opt_out_playback: bool, opt_out_library_stats: bool) {
+        self.privacy["optOutPlayback"] = Value::Bool(opt_out_playback);
+        self.privacy["optOutLibraryStats"] = Value::Bool(opt_out_library_stats);
+    }
+
+    pub(crate) fn webhooks(&self) -> &[String] {
+        &self.webhooks
+    }
+
+    pub(crate) fn set_webhooks(&mut self, webhooks: Vec<String>) {
+        self.webhooks = webhooks;
+    }
+
+    pub(crate) fn create_pin(&mut self, client_identifier: &str) -> &Value {
+        self.last_pin_id += 1;
+        let id = self.last_pin_id;
+        let mut pin: Value = serde_json::from_str(PIN_JSON).expect("pin.json fixture");
+        pin["id"] = Value::from(id);
+        pin["code"] = Value::from(format!("mockpin{}", id));
+        pin["clientIdentifier"] = Value::from(client_identifier);
+        self.pins.push(pin);
+        self.pins.last().unwrap()
+    }
+
+    /// Returns the PIN, if it was created by the client with the same identifier.
+    pub(crate) fn find_pin(&self, id: &str, client_identifier: &str) -> Option<&Value> {
+        let id = id.parse::<u64>().ok();
+        self.pins
+            .iter()
+            .find(|pin| pin["id"].as_u64() == id && pin["clientIdentifier"] == client_identifier)
+    }
+
+    fn find_pin_by_code(&mut self, code: &str) -> Option<&mut Value> {
+        self.pins.iter_mut().find(|pin| pin["code"] == code)
+    }
+
+    pub(crate) fn link_pin(&mut self, code: &str) -> bool {
+        match self.find_pin_by_code(code) {
+            Some(pin) => {
+                pin["authToken"] = Value::from(AUTH_TOKEN);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forgets the PIN, plex.tv responds with 404 to expired PINs.
+    pub(crate) fn expire_pin(&mut self, code: &str) -> bool {
+        let len = self.pins.len();
+        self.pins.retain(|pin| pin["code"] != code);
+        self.pins.len() != len
+    }
+
+    pub(crate) fn users(&self) -> String {
+        self.render(&self.users.to_string())
+    }
+
+    fn friend_list(&mut self) -> &mut Vec<Value> {
+        self.users["MediaContainer"]["User"]
+            .as_array_mut()
+            .expect("users")
+    }
+
+    /// Sets attributes of the friend, which are sent as form parameters. Returns `false` if the
+    /// friend is unknown.
+    pub(crate) fn update_friend(&mut self, id: &str, attributes: &[(String, String)]) -> bool {
+        let id = id.parse::<u64>().ok();
+        let friend = match self
+            .friend_list()
+            .iter_mut()
+            .find(|user| user["id"].as_u64() == id)
+        {
+            Some(friend) => friend,
+            None => return false,
+        };
+
+        for (key, value) in attributes {
+            if friend.get(key).is_some() {
+                friend[key] = attribute_value(&friend[key], value);
+            }
+        }
+        true
+    }
+
+    pub(crate) fn remove_friend(&mut self, id: &str) -> bool {
+        let id = id.parse::<u64>().ok();
+        let users = self.friend_list();
+        let len = users.len();
+        users.retain(|user| user["id"].as_u64() != id);
+        let size = users.len();
+        let container = &mut self.users["MediaContainer"];
+        container["size"] = Value::from(size);
+        container["totalSize"] = Value::from(size);
+        size != len
+    }
+
+    pub(crate) fn server_sections(&self) -> String {
+        self.render(SERVER_SECTIONS_JSON)
+    }
+
+    fn sections(&self) -> Vec<Value> {
+        let sections: Value = serde_json::from_str(SERVER_SECTIONS_JSON).unwrap();
+        sections["MediaContainer"]["Server"][0]["Section"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the sections of the server with `shared` flag, all of them are shared when
+    /// the list of ids is empty.
+    fn shared_sections(&self, section_ids: &[u64]) -> Vec<Value> {
+        self.sections()
+            .into_iter()
+            .map(|mut section| {
+                let shared = section_ids.is_empty()
+                    || section["id"]
+                        .as_u64()
+                        .is_some_and(|id| section_ids.contains(&id));
+                section["shared"] = Value::Bool(shared);
+                section
+            })
+            .collect()
+    }
+
+    pub(crate) fn shared_servers(&self) -> &[Value] {
+        &self.shared_servers
+    }
+
+    /// Shares the server with the user, settings are taken from `sharing_settings` of the
+    /// request.
+    pub(crate) fn share_server(
+        &mut self,
+        invited: &str,
+        section_ids: &[u64],
+        settings: &Value,
+    ) -> &Value {
+        let id = 5000001 + self.shared_servers.len();
+        let setting = |key: &str| settings[key].as_str().unwrap_or_default().to_owned();
+        let invited_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let shared_server = serde_json::json!({
+            "id": id,
+            "username": invited,
+            "email": if invited.contains('@') { invited.to_owned() } else { format!("{}@example.com", invited) },
+            "userID": id + 1000000,
+            "accessToken": "",
+            "name": self.friendly_name(),
+            "invitedAt": invited_at,
+            "allowSync": setting("allowSync") == "1",
+            "allowCameraUpload": setting("allowCameraUpload") == "1",
+            "allowChannels": setting("allowChannels") == "1",
+            "owned": false,
+            "allLibraries": section_ids.is_empty(),
+            "filterAll": "",
+            "filterMovies": setting("filterMovies"),
+            "filterMusic": setting("filterMusic"),
+            "filterPhotos": "",
+            "filterTelevision": setting("filterTelevision"),
+            "Section": self.shared_sections(section_ids),
+        });
+        // The friend has to accept the invite to get access to the server
+        self.sent_invites.push(serde_json::json!({
+            "id": shared_server["userID"],
+            "createdAt": invited_at,
+            "friend": false,
+            "home": false,
+            "server": true,
+            "username": invited,
+            "email": shared_server["email"],
+            "thumb": "",
+            "friendlyName": invited,
+            "Server": [{ "name": self.friendly_name(), "numLibraries": self.sections().len() }],
+        }));
+        self.shared_servers.push(shared_server);
+        self.shared_servers.last().unwrap()
+    }
+
+    pub(crate) fn update_shared_sections(&mut self, id: &str, section_ids: &[u64]) -> bool {
+        let id = id.parse::<u64>().ok();
+        let sections = self.shared_sections(section_ids);
+        match self
+            .shared_servers
+            .iter_mut()
+            .find(|shared_server| shared_server["id"].as_u64() == id)
+        {
+            Some(shared_server) => {
+                shared_server["allLibraries"] = Value::Bool(section_ids.is_empty());
+                shared_server["Section"] = Value::from(sections);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn invites(&self) -> String {
+        self.render(&self.invites.to_string())
+    }
+
+    pub(crate) fn sent_invites(&self) -> String {
+        let mut invites: Value = serde_json::from_str(INVITES_JSON).expect("invites.json fixture");
+        invites["MediaContainer"]["size"] = Value::from(self.sent_invites.len());
+        invites["MediaContainer"]["Invite"] = Value::from(self.sent_invites.clone());
+        invites.to_string()
+    }
+
+    /// Removes the invite, as it happens when it's accepted or rejected.
+    pub(crate) fn remove_invite(&mut self, id: &str) -> bool {
+        let id = id.parse::<u64>().ok();
+        let container = &mut self.invites["MediaContainer"];
+        let invites = container["Invite"].as_array_mut().expect("invites");
+        let len = invites.len();
+        invites.retain(|invite| invite["id"].as_u64() != id);
+        let size = invites.len();
+        container["size"] = Value::from(size);
+        size != len
+    }
+
+    /// Removes the invite, which was sent to a friend, as it happens when it's cancelled.
+    pub(crate) fn remove_sent_invite(&mut self, id: &str) -> bool {
+        let id = id.parse::<u64>().ok();
+        let len = self.sent_invites.len();
+        self.sent_invites
+            .retain(|invite| invite["id"].as_u64() != id);
+        self.sent_invites.len() != len
+    }
+}
+
+/// Converts a form parameter to the type of the attribute it replaces, the way plex.tv stores
+/// `0`/`1` flags as booleans.
+fn attribute_value(current: &Value, value: &str) -> Value {
+    match current {
+        Value::Bool(_) => Value::Bool(value == "1" || value == "true"),
+        Value::Number(_) => value
+            .parse::<u64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| Value::from(value)),
+        _ => Value::from(value),
+    }
+}